@@ -0,0 +1,132 @@
+use std::fs::{create_dir_all, read_to_string, write};
+use std::io;
+
+use crate::solutions::Answer;
+
+/// Load the recorded answers for a day, if there are any. They're kept in files under
+/// `answers/day1` etc, with part 1 on the first line and part 2 on the second.
+pub fn load_answers(day: u8) -> (Option<Answer>, Option<Answer>) {
+    read_to_string(answers_file(day)).ok()
+        .map(|answers| {
+            let mut answers = answers.lines()
+                .map(|a| a.trim())
+                .map(|a| if a.is_empty() { None } else { a.parse().ok() });
+
+            (answers.next().flatten(), answers.next().flatten())
+        })
+        .unwrap_or((None, None))
+}
+
+/// Where the answers for a day are recorded
+pub fn answers_file(day: u8) -> String {
+    format!("answers/day{}", day)
+}
+
+/// Outcome of saving a day's answers
+#[derive(Debug, Eq, PartialEq)]
+pub enum Saved {
+    /// the same answers were already recorded
+    Unchanged,
+    Written,
+    /// different answers were recorded, but have been overwritten. Holds the differences.
+    Replaced(String),
+    /// different answers are recorded, so nothing was written. Holds the differences.
+    Refused(String),
+}
+
+/// Record the answers for a day. Parts which weren't calculated keep any answer which is already
+/// recorded, and recorded answers are only overwritten with different ones if forced.
+pub fn save_answers(day: u8, calculated: [Option<&Answer>; 2], force: bool) -> io::Result<Saved> {
+    let (recorded_1, recorded_2) = load_answers(day);
+    let recorded = [recorded_1.as_ref(), recorded_2.as_ref()];
+    let updated = [calculated[0].or(recorded[0]), calculated[1].or(recorded[1])];
+    if updated == recorded {
+        return Ok(Saved::Unchanged);
+    }
+
+    // filling in missing answers is fine, but changing them needs to be forced
+    let conflicts = recorded.iter().zip(&updated).any(|(r, u)| r.is_some() && r != u);
+    let differences = diff(day, recorded, updated);
+    if conflicts && !force {
+        return Ok(Saved::Refused(differences));
+    }
+
+    let lines = updated.map(|a| a.map(|a| a.to_string()).unwrap_or_default());
+    create_dir_all("answers")?;
+    write(answers_file(day), format!("{}\n{}\n", lines[0], lines[1]))?;
+
+    Ok(if conflicts { Saved::Replaced(differences) } else { Saved::Written })
+}
+
+/// Describe the differences between the recorded and updated answers, line by line
+fn diff(day: u8, recorded: [Option<&Answer>; 2], updated: [Option<&Answer>; 2]) -> String {
+    let mut lines = vec![format!("--- {} (recorded)", answers_file(day)), "+++ calculated".to_owned()];
+    for (recorded, updated) in recorded.into_iter().zip(updated) {
+        match (recorded, updated) {
+            (Some(recorded), Some(updated)) if recorded == updated => lines.push(format!("  {}", recorded)),
+            (Some(recorded), Some(updated)) => {
+                lines.push(format!("- {}", recorded));
+                lines.push(format!("+ {}", updated));
+            }
+            (_, Some(updated)) => lines.push(format!("+ {}", updated)),
+            (_, None) => {}
+        }
+    }
+    lines.join("\n")
+}
+
+/// Outcome of comparing a calculated answer with the recorded one
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub enum Check {
+    Correct,
+    /// holds the answer that was expected
+    Incorrect(Answer),
+    /// there's no recorded answer to compare with
+    Unknown,
+}
+
+impl Check {
+    pub fn of(answer: &Answer, expected: Option<&Answer>) -> Check {
+        match expected {
+            Some(expected) if expected == answer => Check::Correct,
+            Some(expected) => Check::Incorrect(expected.clone()),
+            None => Check::Unknown,
+        }
+    }
+
+    /// Mark to show next to an answer
+    pub fn mark(&self) -> String {
+        match self {
+            Check::Correct => "✓".to_owned(),
+            Check::Incorrect(expected) => format!("✗ (expected {})", expected),
+            Check::Unknown => String::new(),
+        }
+    }
+}
+
+// -------------------------------------------------------------------------------------------------
+// tests
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn check_against_expected() {
+        let answer = Answer::Integer(42);
+        assert_eq!(Check::Correct, Check::of(&answer, Some(&Answer::Unsigned(42))));
+        assert_eq!(Check::Incorrect(Answer::Integer(41)), Check::of(&answer, Some(&Answer::Integer(41))));
+        assert_eq!(Check::Unknown, Check::of(&answer, None));
+        assert_eq!("✗ (expected 41)", Check::Incorrect(Answer::Integer(41)).mark());
+    }
+
+    #[test]
+    fn diff_answers() {
+        let recorded = [Some(&Answer::Integer(288)), Some(&Answer::Integer(1))];
+        let updated = [Some(&Answer::Integer(288)), Some(&Answer::Integer(71503))];
+        assert_eq!("--- answers/day6 (recorded)\n+++ calculated\n  288\n- 1\n+ 71503", diff(6, recorded, updated));
+
+        let updated = [None, Some(&Answer::Integer(71503))];
+        assert_eq!("--- answers/day6 (recorded)\n+++ calculated\n+ 71503", diff(6, [None, None], updated));
+    }
+}
@@ -0,0 +1,186 @@
+use std::collections::HashMap;
+use std::process::Command;
+use std::time::{Duration, Instant, SystemTime, UNIX_EPOCH};
+
+use crate::json::Json;
+use crate::solutions::{Harness, SolveError};
+use crate::visualise::NullVisualiser;
+
+/// Summary of the durations measured over repeated runs of one stage of a solution
+pub struct Stats {
+    pub min: Duration,
+    pub median: Duration,
+    pub mean: Duration,
+    pub max: Duration,
+}
+
+impl Stats {
+    fn of(mut durations: Vec<Duration>) -> Stats {
+        durations.sort();
+
+        // the middle value, or the average of the middle two if there's an even number
+        let n = durations.len();
+        Stats {
+            min: durations[0],
+            median: (durations[(n - 1) / 2] + durations[n / 2]) / 2,
+            mean: durations.iter().sum::<Duration>() / n as u32,
+            max: durations[n - 1],
+        }
+    }
+}
+
+/// Benchmark results for one day. Parsing is only timed for days which expose it.
+pub struct DayBench {
+    pub day: u8,
+    pub parse: Option<Stats>,
+    /// timings for each of the selected parts
+    pub parts: Vec<(u8, Stats)>,
+}
+
+/// Time the given stage `runs` times, failing on the first error
+fn measure<T, F>(runs: usize, stage: F) -> Result<Stats, SolveError>
+    where F: Fn() -> Result<T, SolveError>
+{
+    let mut durations = Vec::with_capacity(runs);
+    for _ in 0..runs {
+        let start = Instant::now();
+        stage()?;
+        durations.push(start.elapsed());
+    }
+    Ok(Stats::of(durations))
+}
+
+/// Run parsing (if available) and the selected parts of a solution `runs` times each
+pub fn bench_day(solution: &dyn Harness, day: u8, parts: &[u8], input: &str, runs: usize) -> Result<DayBench, SolveError> {
+    let runs = runs.max(1);
+
+    let parse = match solution.parse(input) {
+        Some(_) => Some(measure(runs, || solution.parse(input).unwrap_or(Ok(()))).map_err(|e| e.in_day(day))?),
+        None => None,
+    };
+    let parts = parts.iter()
+        .map(|&part| {
+            let stats = match part {
+                1 => measure(runs, || solution.part_1(input, &mut NullVisualiser)),
+                _ => measure(runs, || solution.part_2(input, &mut NullVisualiser)),
+            };
+            stats.map(|s| (part, s)).map_err(|e| e.in_part(day, part))
+        })
+        .collect::<Result<_, _>>()?;
+
+    Ok(DayBench { day, parse, parts })
+}
+
+// -------------------------------------------------------------------------------------------------
+// json summary
+
+/// Write the benchmark results as JSON, along with when and at which commit they were taken, so
+/// that runs can be compared over time
+pub fn to_json(results: &[DayBench], runs: usize) -> String {
+    let timestamp = SystemTime::now().duration_since(UNIX_EPOCH)
+        .map(|d| d.as_secs())
+        .unwrap_or(0);
+    let commit = current_commit()
+        .map(|c| format!("\"{}\"", c))
+        .unwrap_or_else(|| "null".to_owned());
+
+    let days = results.iter()
+        .map(|result| {
+            let mut stages = Vec::new();
+            if let Some(parse) = &result.parse {
+                stages.push(format!("\"parse\": {}", stats_json(parse)));
+            }
+            for (part, stats) in &result.parts {
+                stages.push(format!("\"part_{}\": {}", part, stats_json(stats)));
+            }
+
+            format!("    {{ \"day\": {}, {} }}", result.day, stages.join(", "))
+        })
+        .collect::<Vec<_>>();
+
+    format!("{{\n  \"timestamp\": {},\n  \"commit\": {},\n  \"runs\": {},\n  \"days\": [\n{}\n  ]\n}}\n",
+            timestamp, commit, runs.max(1), days.join(",\n"))
+}
+
+fn stats_json(stats: &Stats) -> String {
+    format!("{{ \"min_ns\": {}, \"median_ns\": {}, \"mean_ns\": {}, \"max_ns\": {} }}",
+            stats.min.as_nanos(), stats.median.as_nanos(), stats.mean.as_nanos(), stats.max.as_nanos())
+}
+
+/// Hash of the checked out git commit, if there is one
+pub fn current_commit() -> Option<String> {
+    let output = Command::new("git").args(["rev-parse", "--short", "HEAD"]).output().ok()?;
+    if !output.status.success() { return None; }
+
+    let commit = String::from_utf8(output.stdout).ok()?;
+    Some(commit.trim().to_owned())
+}
+
+// -------------------------------------------------------------------------------------------------
+// comparison
+
+/// The median durations from a JSON summary of an earlier run, by day and stage (`parse`,
+/// `part_1` or `part_2`)
+pub fn read_medians(json: &str) -> Result<HashMap<(u8, String), Duration>, String> {
+    let summary = Json::parse(json)?;
+    let days = summary.get("days").and_then(Json::as_array).ok_or("No days in the summary")?;
+
+    let mut medians = HashMap::new();
+    for entry in days {
+        let (Some(day), Json::Object(fields)) = (entry.get("day").and_then(Json::as_u8), entry) else {
+            return Err("Invalid day in the summary".to_owned());
+        };
+        for (stage, stats) in fields.iter().filter(|(name, _)| name != "day") {
+            let median = stats.get("median_ns").and_then(Json::as_f64)
+                .ok_or_else(|| format!("No median for day {} {}", day, stage))?;
+            medians.insert((day, stage.clone()), Duration::from_nanos(median as u64));
+        }
+    }
+    Ok(medians)
+}
+
+/// How a duration compares with an earlier one, eg. `2.50x faster`
+pub fn change(before: &Duration, after: &Duration) -> String {
+    let ratio = before.as_secs_f64() / after.as_secs_f64().max(f64::MIN_POSITIVE);
+    match ratio >= 1.0 {
+        true => format!("{:.2}x faster", ratio),
+        false => format!("{:.2}x slower", 1.0 / ratio),
+    }
+}
+
+// -------------------------------------------------------------------------------------------------
+// tests
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn stats_summary() {
+        let millis = |values: &[u64]| values.iter().map(|&v| Duration::from_millis(v)).collect();
+
+        let stats = Stats::of(millis(&[5, 1, 9, 3]));
+        assert_eq!(Duration::from_millis(1), stats.min);
+        assert_eq!(Duration::from_millis(4), stats.median);
+        assert_eq!(Duration::from_millis(9), stats.max);
+        assert_eq!(Duration::from_micros(4500), stats.mean);
+
+        let stats = Stats::of(millis(&[7, 2, 3]));
+        assert_eq!(Duration::from_millis(3), stats.median);
+    }
+
+    #[test]
+    fn compare_with_summary() {
+        let stats = |ms: u64| Stats::of(vec![Duration::from_millis(ms)]);
+        let results = [DayBench { day: 14, parse: Some(stats(1)), parts: vec![(2, stats(30))] }];
+
+        let medians = read_medians(&to_json(&results, 1)).unwrap();
+        assert_eq!(Some(&Duration::from_millis(1)), medians.get(&(14, "parse".to_owned())));
+        assert_eq!(Some(&Duration::from_millis(30)), medians.get(&(14, "part_2".to_owned())));
+        assert_eq!(2, medians.len());
+
+        assert_eq!("2.50x faster", change(&Duration::from_millis(30), &Duration::from_millis(12)));
+        assert_eq!("1.50x slower", change(&Duration::from_millis(10), &Duration::from_millis(15)));
+        assert!(read_medians("{\"days\": [{\"day\": 1, \"part_1\": {}}]}").is_err());
+    }
+}
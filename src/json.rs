@@ -0,0 +1,206 @@
+/// Just enough JSON to read back the files written by this program, like visualisation traces and
+/// benchmark summaries
+#[derive(Debug, Clone, PartialEq)]
+pub enum Json {
+    Null,
+    Bool(bool),
+    Number(f64),
+    String(String),
+    Array(Vec<Json>),
+    Object(Vec<(String, Json)>),
+}
+
+impl Json {
+    pub fn parse(text: &str) -> Result<Json, String> {
+        let mut parser = Parser { chars: text.chars().collect(), pos: 0 };
+        let value = parser.value()?;
+        parser.skip_whitespace();
+        match parser.pos == parser.chars.len() {
+            true => Ok(value),
+            false => Err(format!("Unexpected '{}' at column {}", parser.chars[parser.pos], parser.pos + 1)),
+        }
+    }
+
+    pub fn get(&self, key: &str) -> Option<&Json> {
+        match self {
+            Json::Object(fields) => fields.iter().find(|(k, _)| k == key).map(|(_, v)| v),
+            _ => None,
+        }
+    }
+
+    pub fn as_str(&self) -> Option<&str> {
+        match self {
+            Json::String(s) => Some(s),
+            _ => None,
+        }
+    }
+
+    pub fn as_array(&self) -> Option<&[Json]> {
+        match self {
+            Json::Array(values) => Some(values),
+            _ => None,
+        }
+    }
+
+    pub fn as_f64(&self) -> Option<f64> {
+        match self {
+            Json::Number(n) => Some(*n),
+            _ => None,
+        }
+    }
+
+    /// The number, if it's a whole number small enough to have been written exactly
+    pub fn as_i64(&self) -> Option<i64> {
+        match self {
+            Json::Number(n) if n.fract() == 0.0 && n.abs() <= (1u64 << 53) as f64 => Some(*n as i64),
+            _ => None,
+        }
+    }
+
+    pub fn as_u8(&self) -> Option<u8> {
+        match self {
+            Json::Number(n) if n.fract() == 0.0 && (0.0..=255.0).contains(n) => Some(*n as u8),
+            _ => None,
+        }
+    }
+}
+
+struct Parser {
+    chars: Vec<char>,
+    pos: usize,
+}
+
+impl Parser {
+    fn skip_whitespace(&mut self) {
+        while self.chars.get(self.pos).is_some_and(|c| c.is_whitespace()) {
+            self.pos += 1;
+        }
+    }
+
+    fn next(&mut self) -> Result<char, String> {
+        let c = *self.chars.get(self.pos).ok_or("Unexpected end of line")?;
+        self.pos += 1;
+        Ok(c)
+    }
+
+    fn expect(&mut self, expected: char) -> Result<(), String> {
+        self.skip_whitespace();
+        match self.next()? {
+            c if c == expected => Ok(()),
+            c => Err(format!("Expected '{}' but found '{}' at column {}", expected, c, self.pos)),
+        }
+    }
+
+    fn value(&mut self) -> Result<Json, String> {
+        self.skip_whitespace();
+        match self.chars.get(self.pos) {
+            Some('{') => self.object(),
+            Some('[') => self.array(),
+            Some('"') => self.string().map(Json::String),
+            Some('t') => self.literal("true", Json::Bool(true)),
+            Some('f') => self.literal("false", Json::Bool(false)),
+            Some('n') => self.literal("null", Json::Null),
+            Some(_) => self.number(),
+            None => Err("Unexpected end of line".to_owned()),
+        }
+    }
+
+    fn object(&mut self) -> Result<Json, String> {
+        let fields = self.sequence('{', '}', |parser| {
+            parser.skip_whitespace();
+            let key = parser.string()?;
+            parser.expect(':')?;
+            Ok((key, parser.value()?))
+        })?;
+        Ok(Json::Object(fields))
+    }
+
+    fn array(&mut self) -> Result<Json, String> {
+        Ok(Json::Array(self.sequence('[', ']', Parser::value)?))
+    }
+
+    /// Items separated by commas, between the given brackets
+    fn sequence<T, F>(&mut self, open: char, close: char, item: F) -> Result<Vec<T>, String>
+        where F: Fn(&mut Parser) -> Result<T, String>
+    {
+        self.expect(open)?;
+        let mut items = Vec::new();
+        self.skip_whitespace();
+        if self.chars.get(self.pos) == Some(&close) {
+            self.pos += 1;
+            return Ok(items);
+        }
+        loop {
+            items.push(item(self)?);
+            self.skip_whitespace();
+            match self.next()? {
+                ',' => continue,
+                c if c == close => return Ok(items),
+                c => return Err(format!("Expected ',' or '{}' but found '{}' at column {}", close, c, self.pos)),
+            }
+        }
+    }
+
+    fn string(&mut self) -> Result<String, String> {
+        self.expect('"')?;
+        let mut result = String::new();
+        loop {
+            match self.next()? {
+                '"' => return Ok(result),
+                '\\' => match self.next()? {
+                    'n' => result.push('\n'),
+                    'r' => result.push('\r'),
+                    't' => result.push('\t'),
+                    'b' => result.push('\u{8}'),
+                    'f' => result.push('\u{c}'),
+                    'u' => {
+                        let hex = (0..4).map(|_| self.next()).collect::<Result<String, _>>()?;
+                        let code = u32::from_str_radix(&hex, 16).map_err(|_| format!("Invalid escape: '\\u{}'", hex))?;
+                        result.push(char::from_u32(code).unwrap_or(char::REPLACEMENT_CHARACTER));
+                    }
+                    c => result.push(c),
+                },
+                c => result.push(c),
+            }
+        }
+    }
+
+    fn literal(&mut self, word: &str, value: Json) -> Result<Json, String> {
+        let end = self.pos + word.chars().count();
+        match self.chars.get(self.pos..end) {
+            Some(chars) if chars.iter().copied().eq(word.chars()) => {
+                self.pos = end;
+                Ok(value)
+            }
+            _ => Err(format!("Invalid value at column {}", self.pos + 1)),
+        }
+    }
+
+    fn number(&mut self) -> Result<Json, String> {
+        let start = self.pos;
+        while self.chars.get(self.pos).is_some_and(|c| c.is_ascii_digit() || "+-.eE".contains(*c)) {
+            self.pos += 1;
+        }
+        let text = self.chars[start..self.pos].iter().collect::<String>();
+        text.parse().map(Json::Number).map_err(|_| format!("Invalid value at column {}", start + 1))
+    }
+}
+
+// -------------------------------------------------------------------------------------------------
+// tests
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_json() {
+        assert_eq!(Ok(Json::Object(vec![
+            ("a".to_owned(), Json::Array(vec![Json::Number(1.5), Json::Null, Json::Bool(true)])),
+            ("b".to_owned(), Json::String("\u{e9}".to_owned())),
+        ])), Json::parse(" { \"a\" : [1.5, null, true], \"b\": \"\\u00e9\" } "));
+        assert_eq!(Ok(Json::Array(vec![])), Json::parse("[]"));
+        assert!(Json::parse("[1,]").is_err());
+        assert!(Json::parse("[1] 2").is_err());
+    }
+}
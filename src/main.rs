@@ -1,87 +1,588 @@
-use std::fs::read_to_string;
+use std::error::Error;
+use std::fs::{read_to_string, write};
 use std::io;
+use std::process;
+use std::str::FromStr;
 
-use clap::Parser;
+use clap::{Parser, Subcommand};
 
+use crate::answers::{Check, Saved};
+use crate::bench::{DayBench, Stats};
+use crate::runner::{DayResult, format_duration, PartResult};
+use crate::solutions::Answer;
+use crate::report::{DayReport, InputStats, PartReport};
+use crate::visualise::{Graph, ImageFormat, Settings, Target, Theme, Visualiser};
+
+mod answers;
+mod bench;
+mod json;
+mod report;
+mod runner;
 mod solutions;
 mod utils;
+mod visualise;
 
 #[derive(Parser, Debug)]
+#[command(args_conflicts_with_subcommands = true, subcommand_negates_reqs = true)]
 struct Args {
-    day: u8,
+    #[command(subcommand)]
+    command: Option<Command>,
+
+    /// Day to run: a single day (`5`), a range (`1..=12`), or `all`
+    #[arg(required = true)]
+    days: Option<Days>,
 
     #[arg(short, long, required = false)]
     input: Option<String>,
 
-    #[arg(short, long, required = false)]
-    visualise: bool,
+    #[command(flatten)]
+    output: Output,
+
+    /// Only run this part (1 or 2), instead of both
+    #[arg(short, long, value_parser = clap::value_parser!(u8).range(1..=2))]
+    part: Option<u8>,
+
+    /// Fail if any answer doesn't match the one recorded in `answers/dayN`
+    #[arg(long)]
+    check: bool,
+
+    /// Record the answers in `answers/dayN` after a successful run
+    #[arg(long)]
+    save_answers: bool,
+
+    /// Let --save-answers replace answers which are already recorded with different ones
+    #[arg(long, requires = "save_answers")]
+    force: bool,
+
+    /// Write an HTML report of the answers, timings and visualisations to this file
+    #[arg(long, value_name = "FILE")]
+    report: Option<String>,
+
+    /// Write the day's input as a Graphviz DOT graph to this file, instead of solving it
+    #[arg(long, value_name = "FILE")]
+    dot: Option<String>,
+
+    /// Write the day's input as a Mermaid flowchart to this file, instead of solving it
+    #[arg(long, value_name = "FILE", conflicts_with = "dot")]
+    mermaid: Option<String>,
+
+    /// Benchmark the selected days, running each part this many times
+    #[arg(long, value_name = "N")]
+    bench: Option<usize>,
+
+    /// Where to write the JSON summary of a benchmark run
+    #[arg(long, value_name = "FILE", default_value = "bench.json")]
+    bench_output: String,
+
+    /// Compare a benchmark run with the JSON summary of an earlier one, eg. from another commit
+    #[arg(long, value_name = "FILE", requires = "bench")]
+    bench_baseline: Option<String>,
+}
+
+impl Args {
+    /// The parts selected to run
+    fn parts(&self) -> Vec<u8> {
+        self.part.map(|part| vec![part]).unwrap_or_else(|| vec![1, 2])
+    }
+
+    /// The parts selected to run which have been solved for the given day
+    fn parts_of(&self, day: u8) -> Vec<u8> {
+        let solved = solutions::solved_parts(day).unwrap_or(&[]);
+        self.parts().into_iter().filter(|p| solved.contains(p)).collect()
+    }
+}
+
+/// Where visual output goes, when running days or replaying a trace
+#[derive(clap::Args, Debug, Clone)]
+#[command(about = None, long_about = None)]
+struct Output {
+    /// Show how the answers are found: in the `terminal`, animated in place (`animate`), written
+    /// to a file, or `none`
+    #[arg(short, long, value_name = "TARGET", num_args = 0..=1, require_equals = true,
+          default_value = "none", default_missing_value = "terminal")]
+    visualise: Target,
+
+    /// Draw the visualisation as an image instead: the final frame as a `.png`, all of them
+    /// animated as a `.gif`, or outlines as an `.svg`
+    #[arg(long, value_name = "FILE", value_parser = image_path, conflicts_with = "visualise")]
+    visualise_out: Option<String>,
+
+    /// Record the frames and events shown as JSON lines instead, to `replay` later
+    #[arg(long, value_name = "FILE", conflicts_with_all = ["visualise", "visualise_out"])]
+    trace: Option<String>,
+
+    /// Frames per second when animating visualisations
+    #[arg(long, value_name = "N", default_value_t = 10)]
+    fps: u32,
+
+    /// Colours to show visualisations in, in the terminal, images and reports: default,
+    /// high-contrast, colour-blind or monochrome
+    #[arg(long, value_name = "NAME", default_value = "default")]
+    theme: Theme,
 }
 
-fn main() -> io::Result<()> {
+impl Output {
+    fn target(&self) -> Target {
+        match (&self.visualise_out, &self.trace) {
+            (Some(path), _) => Target::Image(path.clone()),
+            (_, Some(path)) => Target::Trace(path.clone()),
+            _ => self.visualise.clone(),
+        }
+    }
+
+    fn visualiser(&self) -> io::Result<Box<dyn Visualiser>> {
+        self.target().create(&Settings { fps: self.fps, theme: self.theme })
+    }
+}
+
+/// Check that an image will be written in a supported format
+fn image_path(path: &str) -> Result<String, String> {
+    match ImageFormat::of(path) {
+        Some(_) => Ok(path.to_owned()),
+        None => Err("Images must be written to a .png, .gif or .svg file".to_owned()),
+    }
+}
+
+#[derive(Subcommand, Debug)]
+enum Command {
+    /// List the available days
+    List,
+    /// Show a trace recorded with --trace again. It's shown in the terminal, unless another
+    /// visualiser is chosen.
+    Replay {
+        /// The recorded trace
+        file: String,
+
+        #[command(flatten)]
+        output: Output,
+    },
+}
+
+fn main() {
     let args = Args::parse();
 
-    // load input data
-    println!("\nRunning day: {}", args.day);
-    let input = args.input
-        .map(|filename| read_to_string(&filename))
-        .unwrap_or_else(|| load_input(args.day))?;
+    if let Err(e) = run(args) {
+        eprintln!("\nError: {}", e);
+        process::exit(1);
+    }
+}
+
+fn run(args: Args) -> Result<(), Box<dyn Error>> {
+    let days = match (&args.command, &args.days) {
+        (Some(Command::List), _) => return list(),
+        (Some(Command::Replay { file, output }), _) => return replay(file, output),
+        (None, Some(days)) => days,
+        (None, None) => return Err("No days selected".into()),
+    };
+
+    if let Some(runs) = args.bench {
+        return run_bench(days, runs, &args);
+    }
+    if let Some(path) = &args.dot {
+        let day = days.single().ok_or("--dot can only be used with a single day")?;
+        return write_graph(day, path, Graph::to_dot, &args);
+    }
+    if let Some(path) = &args.mermaid {
+        let day = days.single().ok_or("--mermaid can only be used with a single day")?;
+        return write_graph(day, path, Graph::to_mermaid, &args);
+    }
+
+    match days.single() {
+        Some(day) => run_single(day, &args),
+        None if args.input.is_some() => Err("--input can only be used when running a single day".into()),
+        None => run_many(&days.list(), &args),
+    }
+}
+
+/// Show all the available days, and which parts of them have been solved
+fn list() -> Result<(), Box<dyn Error>> {
+    let rows = solutions::list().iter()
+        .map(|s| {
+            let parts = s.parts.iter().map(|p| p.to_string()).collect::<Vec<_>>().join(", ");
+            vec![s.day.to_string(), s.title.to_owned(), parts]
+        })
+        .collect::<Vec<_>>();
+
+    println!();
+    print_table(&["Day", "Title", "Parts"], &rows);
+    Ok(())
+}
+
+/// Send a recorded trace to the chosen visualiser
+fn replay(file: &str, output: &Output) -> Result<(), Box<dyn Error>> {
+    let trace = read_to_string(file)?;
+
+    let mut output = output.clone();
+    if output.target() == Target::None { output.visualise = Target::Terminal; }
+    let mut visualiser = output.visualiser()?;
+
+    let records = visualise::replay(&trace, visualiser.as_mut()).map_err(|e| format!("{} of {}", e, file))?;
+    visualiser.finish()?;
+    println!("\nReplayed {} records from: {}", records, file);
+
+    Ok(())
+}
+
+/// Run the selected parts of a single day, showing the answers as they're calculated, and whether
+/// they match the recorded answers
+fn run_single(day: u8, args: &Args) -> Result<(), Box<dyn Error>> {
+    if (args.check || args.save_answers) && args.input.is_some() {
+        return Err("Recorded answers are for the day's own input, so can't be checked or saved with --input".into());
+    }
+
+    let solution = solutions::get_solution(day)
+        .ok_or_else(|| format!("No solution for day {}", day))?;
+    let parts = args.parts_of(day);
+    if let (Some(part), true) = (args.part, parts.is_empty()) {
+        return Err(format!("Part {} of day {} hasn't been solved", part, day).into());
+    }
+
+    // load input data, and the recorded answers which go with it
+    println!("\nRunning day: {}", day);
+    let (expected_1, expected_2) = if args.input.is_none() { answers::load_answers(day) } else { (None, None) };
+    let expected = [expected_1, expected_2];
+    let input = args.input.as_ref()
+        .map(read_to_string)
+        .unwrap_or_else(|| load_input(day))?;
+
+    // execute each part
+    let mut visualiser = args.output.visualiser()?;
+    let mut answers = [None, None];
+    let mut checks = Vec::new();
+    let mut reports = Vec::new();
+    for (i, &part) in parts.iter().enumerate() {
+        if args.output.target() == Target::Terminal && i > 0 { println!(); }
+
+        let PartResult { answer, duration, .. } = runner::run_part(solution.as_ref(), day, part, &input, visualiser.as_mut());
+        let answer = answer?;
+        let check = Check::of(&answer, expected[part as usize - 1].as_ref());
+        println!("\nPart {}: {}", part, marked(format!("{}  ({})", answer, format_duration(&duration)), &check));
+
+        reports.push(PartReport { part, answer: Ok(answer.clone()), duration, check: check.clone() });
+        answers[part as usize - 1] = Some(answer);
+        checks.push(check);
+    }
+    visualiser.finish()?;
+
+    if let Some(path) = &args.report {
+        let (frame, drawing) = runner::capture_day(solution.as_ref(), day, &parts, &input);
+        let report = DayReport { day, parts: reports, input: InputStats::of(&input), frame, drawing };
+        report::write_report(path, &[report], args.output.theme)?;
+        println!("\nReport written to: {}", path);
+    }
+
+    if args.save_answers {
+        save(day, &answers, args.force)?;
+    }
+
+    if args.check {
+        if checks.iter().all(|c| *c == Check::Unknown) {
+            return Err(format!("No recorded answers to check for day {}", day).into());
+        }
+        if checks.iter().any(|c| matches!(c, Check::Incorrect(_))) {
+            return Err(format!("Day {} doesn't match the recorded answers", day).into());
+        }
+    }
+
+    Ok(())
+}
+
+/// Run several days, and summarise the results in a table
+fn run_many(days: &[u8], args: &Args) -> Result<(), Box<dyn Error>> {
+    let mut rows = Vec::new();
+    let mut errors = Vec::new();
+    let mut mismatches = 0;
+    let mut solved = Vec::new();
+    let mut reports = Vec::new();
+    let mut visualiser = args.output.visualiser()?;
+
+    for &day in days {
+        let input = match load_input(day) {
+            Ok(input) => input,
+            Err(_) => {
+                rows.push(vec![day.to_string(), "skipped (no input)".to_owned()]);
+                continue;
+            }
+        };
+
+        let Some(solution) = solutions::get_solution(day) else { continue };
+        let selected = args.parts_of(day);
+        let DayResult { day, parts } = runner::run_day(solution.as_ref(), day, &selected, &input, visualiser.as_mut());
+        let (expected_1, expected_2) = answers::load_answers(day);
+        let expected = [expected_1, expected_2];
+
+        let mut row = vec![day.to_string()];
+        let mut answers = [None, None];
+        let mut failed = false;
+        let mut part_reports = Vec::new();
+        let mut parts = parts.into_iter().peekable();
+        for part in args.parts() {
+            // parts which haven't been solved for this day aren't run
+            let Some(PartResult { answer, duration, .. }) = parts.next_if(|r| r.part == part) else {
+                row.extend(["-".to_owned(), "-".to_owned()]);
+                continue;
+            };
+            match answer {
+                Ok(answer) => {
+                    let check = Check::of(&answer, expected[part as usize - 1].as_ref());
+                    if matches!(check, Check::Incorrect(_)) { mismatches += 1; }
+                    row.push(marked(answer.to_string(), &check));
+                    part_reports.push(PartReport { part, answer: Ok(answer.clone()), duration, check });
+                    answers[part as usize - 1] = Some(answer);
+                }
+                Err(e) => {
+                    row.push("error".to_owned());
+                    part_reports.push(PartReport { part, answer: Err(e.to_string()), duration, check: Check::Unknown });
+                    errors.push(e);
+                    failed = true;
+                }
+            }
+            row.push(format_duration(&duration));
+        }
+        rows.push(row);
+        if !failed {
+            solved.push((day, answers));
+        }
+        if args.report.is_some() {
+            let (frame, drawing) = runner::capture_day(solution.as_ref(), day, &selected, &input);
+            reports.push(DayReport { day, parts: part_reports, input: InputStats::of(&input), frame, drawing });
+        }
+    }
+
+    visualiser.finish()?;
+
+    let mut headers = vec!["Day"];
+    for part in args.parts() {
+        headers.push(if part == 1 { "Part 1" } else { "Part 2" });
+        headers.push("Time");
+    }
+    println!();
+    print_table(&headers, &rows);
+
+    if let Some(path) = &args.report {
+        report::write_report(path, &reports, args.output.theme)?;
+        println!("\nReport written to: {}", path);
+    }
+
+    // only days where every selected part succeeded have answers to save
+    let mut refused = 0;
+    if args.save_answers {
+        for (day, answers) in &solved {
+            if let Err(e) = save(*day, answers, args.force) {
+                eprintln!("\nError: {}", e);
+                refused += 1;
+            }
+        }
+    }
+
+    for e in &errors { eprintln!("\nError: {}", e); }
+    if !errors.is_empty() {
+        Err(format!("{} part(s) failed", errors.len()).into())
+    } else if refused > 0 {
+        Err(format!("Answers for {} day(s) weren't saved", refused).into())
+    } else if args.check && mismatches > 0 {
+        Err(format!("{} answer(s) don't match the recorded answers", mismatches).into())
+    } else {
+        Ok(())
+    }
+}
+
+/// Write the graph a day's input describes, for the days where it's a network
+fn write_graph(day: u8, path: &str, format: fn(&Graph) -> String, args: &Args) -> Result<(), Box<dyn Error>> {
+    let solution = solutions::get_solution(day)
+        .ok_or_else(|| format!("No solution for day {}", day))?;
+    let input = args.input.as_ref()
+        .map(read_to_string)
+        .unwrap_or_else(|| load_input(day))?;
+
+    let graph = solution.graph(&input)
+        .ok_or_else(|| format!("Day {} doesn't have a graph to export", day))?
+        .map_err(|e| e.in_day(day))?;
+    write(path, format(&graph))?;
+    println!("Graph written to: {}", path);
+
+    Ok(())
+}
+
+/// Record a day's answers, showing any differences from those already recorded
+fn save(day: u8, answers: &[Option<Answer>; 2], force: bool) -> Result<(), Box<dyn Error>> {
+    let file = answers::answers_file(day);
+    match answers::save_answers(day, [answers[0].as_ref(), answers[1].as_ref()], force)? {
+        Saved::Unchanged => println!("\nAnswers already recorded in: {}", file),
+        Saved::Written => println!("\nAnswers saved to: {}", file),
+        Saved::Replaced(diff) => println!("\n{}\n\nAnswers replaced in: {}", diff, file),
+        Saved::Refused(diff) => {
+            println!("\n{}", diff);
+            return Err(format!("Not replacing the different answers in {} (use --force to replace them)", file).into());
+        }
+    }
+    Ok(())
+}
+
+/// Add the mark showing whether an answer was correct, if it's known
+fn marked(value: String, check: &Check) -> String {
+    match check {
+        Check::Unknown => value,
+        _ => format!("{} {}", value, check.mark()),
+    }
+}
+
+/// Benchmark the selected days, summarising the timings in a table and writing them to a JSON file
+fn run_bench(days: &Days, runs: usize, args: &Args) -> Result<(), Box<dyn Error>> {
+    let inputs = match (days.single(), &args.input) {
+        (Some(day), Some(filename)) => vec![(day, read_to_string(filename)?)],
+        (None, Some(_)) => return Err("--input can only be used when running a single day".into()),
+        (_, None) => days.list().into_iter()
+            .filter_map(|day| load_input(day).ok().map(|input| (day, input)))
+            .collect(),
+    };
 
-    // execute part 1
-    let solution = solutions::get_solution(args.day);
-    let ans_1 = solution.part_1(&input, args.visualise);
-    println!("\nPart 1: {}", ans_1);
+    let baseline = match &args.bench_baseline {
+        Some(path) => Some(bench::read_medians(&read_to_string(path)?)
+            .map_err(|e| format!("Can't compare with {}: {}", path, e))?),
+        None => None,
+    };
 
-    // execute part 2
-    if args.visualise { println!(); }
-    let ans_2 = solution.part_2(&input, args.visualise);
-    println!("\nPart 2: {}", ans_2);
+    let mut results = Vec::new();
+    for (day, input) in inputs {
+        println!("Benchmarking day {} ({} runs)", day, runs);
+        let solution = solutions::get_solution(day)
+            .ok_or_else(|| format!("No solution for day {}", day))?;
+        results.push(bench::bench_day(solution.as_ref(), day, &args.parts_of(day), &input, runs)?);
+    }
+
+    let mut rows = Vec::new();
+    for DayBench { day, parse, parts } in &results {
+        // each stage is named as it is in the JSON summary, and as it's shown in the table
+        let stages = parse.iter().map(|stats| ("parse".to_owned(), "Parse".to_owned(), stats))
+            .chain(parts.iter().map(|(part, stats)| (format!("part_{}", part), format!("Part {}", part), stats)));
+        for (key, stage, Stats { min, median, mean, max }) in stages {
+            let mut row = vec![day.to_string(), stage, format_duration(min),
+                               format_duration(median), format_duration(mean), format_duration(max)];
+            if let Some(baseline) = &baseline {
+                row.push(baseline.get(&(*day, key))
+                    .map(|before| bench::change(before, median))
+                    .unwrap_or_else(|| "-".to_owned()));
+            }
+            rows.push(row);
+        }
+    }
+
+    let mut headers = vec!["Day", "Stage", "Min", "Median", "Mean", "Max"];
+    if baseline.is_some() { headers.push("Median vs baseline"); }
+    println!();
+    print_table(&headers, &rows);
+
+    write(&args.bench_output, bench::to_json(&results, runs))?;
+    println!("\nSummary written to: {}", args.bench_output);
 
     Ok(())
 }
 
+/// Print rows of values as a table, with columns sized to fit. Short rows are allowed
+/// (eg. for skipped days), the last value in the row just runs on past the column boundaries.
+fn print_table(headers: &[&str], rows: &[Vec<String>]) {
+    let mut widths = headers.iter().map(|h| h.chars().count()).collect::<Vec<_>>();
+    for row in rows.iter().filter(|r| r.len() == headers.len()) {
+        for (i, value) in row.iter().enumerate() {
+            widths[i] = widths[i].max(value.chars().count());
+        }
+    }
+
+    let format_row = |row: &[String]| row.iter().enumerate()
+        .map(|(i, v)| if i + 1 < row.len() { format!("{:w$}", v, w = widths[i]) } else { v.clone() })
+        .collect::<Vec<_>>()
+        .join(" | ");
+
+    println!("{}", format_row(&headers.iter().map(|h| h.to_string()).collect::<Vec<_>>()));
+    println!("{}", widths.iter().map(|w| "-".repeat(*w)).collect::<Vec<_>>().join("-+-"));
+    for row in rows {
+        println!("{}", format_row(row));
+    }
+}
+
 fn load_input(day_num: u8) -> io::Result<String> {
     read_to_string(format!("inputs/day{}", day_num))
 }
 
+// -------------------------------------------------------------------------------------------------
+// day selection
+
+/// Which days to run
+#[derive(Debug, Clone, Eq, PartialEq)]
+enum Days {
+    All,
+    /// inclusive range of days
+    Range(u8, u8),
+}
+
+impl Days {
+    /// The one day selected, if only one was asked for
+    fn single(&self) -> Option<u8> {
+        match self {
+            Days::Range(first, last) if first == last => Some(*first),
+            _ => None,
+        }
+    }
+
+    /// All the selected days which have solutions
+    fn list(&self) -> Vec<u8> {
+        solutions::available_days().into_iter()
+            .filter(|day| match self {
+                Days::All => true,
+                Days::Range(first, last) => first <= day && day <= last,
+            })
+            .collect()
+    }
+}
+
+impl FromStr for Days {
+    type Err = String;
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        let parse_day = |d: &str| d.trim().parse::<u8>()
+            .map_err(|_| format!("Invalid day: '{}'", d));
+
+        let range = |first: u8, last: Option<u8>| match last {
+            Some(last) if first <= last => Ok(Days::Range(first, last)),
+            _ => Err(format!("Empty range: '{}'", value)),
+        };
+
+        if value == "all" {
+            Ok(Days::All)
+        } else if let Some((first, last)) = value.split_once("..=") {
+            range(parse_day(first)?, Some(parse_day(last)?))
+        } else if let Some((first, last)) = value.split_once("..") {
+            range(parse_day(first)?, parse_day(last)?.checked_sub(1))
+        } else {
+            let day = parse_day(value)?;
+            Ok(Days::Range(day, day))
+        }
+    }
+}
+
 // -------------------------------------------------------------------------------------------------
 // tests
 
 #[cfg(test)]
 mod tests {
-    use std::fs::read_to_string;
-
-    use crate::load_input;
-    use crate::solutions::get_solution;
+    use crate::{Days, load_input};
+    use crate::answers::load_answers;
+    use crate::solutions::{get_solution, Answer};
+    use crate::visualise::NullVisualiser;
 
     /// Run solution to part 1 and check against expected answer
-    fn expect_part_1(day_num: u8, answer: i64) {
+    fn expect_part_1(day_num: u8, answer: Answer) {
         let input = load_input(day_num).unwrap();
-        let solution = get_solution(day_num);
-        assert_eq!(solution.part_1(&input, false), answer, "Day {}, Part 1 should be: {}", day_num, answer);
+        let solution = get_solution(day_num).unwrap();
+        assert_eq!(solution.part_1(&input, &mut NullVisualiser), Ok(answer.clone()), "Day {}, Part 1 should be: {}", day_num, answer);
     }
 
     /// Run solution to part 1 and check against expected answer
-    fn expect_part_2(day_num: u8, answer: i64) {
+    fn expect_part_2(day_num: u8, answer: Answer) {
         let input = load_input(day_num).unwrap();
-        let solution = get_solution(day_num);
-        assert_eq!(solution.part_2(&input, false), answer, "Day {}, Part 2 should be: {}", day_num, answer);
-    }
-
-    fn load_answers(day_num: u8) -> (Option<i64>, Option<i64>) {
-        // Answers, if they exist, should be in files under answers/day1 etc
-        read_to_string(format!("answers/day{}", day_num)).ok()
-            .map(|answers| {
-                let answers = answers.lines()
-                    .map(|a| a.parse().unwrap())
-                    .collect::<Vec<_>>();
-
-                match answers.len() {
-                    0 => (None, None),
-                    1 => (Some(answers[0]), None),
-                    _ => (Some(answers[0]), Some(answers[1]))
-                }
-            })
-            .unwrap_or((None, None))
+        let solution = get_solution(day_num).unwrap();
+        assert_eq!(solution.part_2(&input, &mut NullVisualiser), Ok(answer.clone()), "Day {}, Part 2 should be: {}", day_num, answer);
     }
 
     #[test]
@@ -98,4 +599,17 @@ mod tests {
             }
         }
     }
+
+    #[test]
+    fn parse_days() {
+        assert_eq!(Ok(Days::All), "all".parse());
+        assert_eq!(Ok(Days::Range(5, 5)), "5".parse());
+        assert_eq!(Ok(Days::Range(1, 12)), "1..=12".parse());
+        assert_eq!(Ok(Days::Range(1, 11)), "1..12".parse());
+        assert!("day5".parse::<Days>().is_err());
+        assert!("..".parse::<Days>().is_err());
+        assert_eq!(Err("Empty range: '5..=3'".to_owned()), "5..=3".parse::<Days>());
+        assert_eq!(Err("Empty range: '3..3'".to_owned()), "3..3".parse::<Days>());
+        assert_eq!(Err("Empty range: '1..0'".to_owned()), "1..0".parse::<Days>());
+    }
 }
\ No newline at end of file
@@ -0,0 +1,240 @@
+use std::fs::write;
+use std::io;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+use crate::answers::Check;
+use crate::bench::current_commit;
+use crate::runner::format_duration;
+use crate::solutions::{self, Answer};
+use crate::visualise::{frame_png, Drawing, Frame, Style, Theme};
+
+/// Everything about one day's run to include in a report
+pub struct DayReport {
+    pub day: u8,
+    pub parts: Vec<PartReport>,
+    pub input: InputStats,
+    /// the last frame and drawing shown while solving
+    pub frame: Option<Frame>,
+    pub drawing: Option<Drawing>,
+}
+
+pub struct PartReport {
+    pub part: u8,
+    /// the answer, or a description of what went wrong
+    pub answer: Result<Answer, String>,
+    pub duration: Duration,
+    pub check: Check,
+}
+
+/// Size of a day's input
+pub struct InputStats {
+    pub bytes: usize,
+    pub lines: usize,
+    /// length of the longest line
+    pub width: usize,
+}
+
+impl InputStats {
+    pub fn of(input: &str) -> InputStats {
+        InputStats {
+            bytes: input.len(),
+            lines: input.lines().count(),
+            width: input.lines().map(|l| l.chars().count()).max().unwrap_or(0),
+        }
+    }
+}
+
+/// Write a report of a run as a single HTML file, with any visualisations embedded in it, so that
+/// it can be shared as it is. Frames are shown in their styles, adjusted by the theme.
+pub fn write_report(path: &str, days: &[DayReport], theme: Theme) -> io::Result<()> {
+    write(path, to_html(days, theme)?)
+}
+
+fn to_html(days: &[DayReport], theme: Theme) -> io::Result<String> {
+    let timestamp = SystemTime::now().duration_since(UNIX_EPOCH)
+        .map(|d| d.as_secs())
+        .unwrap_or(0);
+    let commit = current_commit().unwrap_or_else(|| "unknown".to_owned());
+
+    let mut html = vec![
+        "<!DOCTYPE html>".to_owned(),
+        "<html>\n<head>\n<meta charset=\"utf-8\">\n<title>Advent of Code 2023</title>".to_owned(),
+        format!("<style>{}</style>", STYLE),
+        "</head>\n<body>\n<h1>Advent of Code 2023</h1>".to_owned(),
+        format!("<p class=\"about\">Commit {}, run at <span class=\"time\" data-timestamp=\"{}\">{} seconds since 1970</span></p>",
+                escape(&commit), timestamp, timestamp),
+    ];
+
+    // summary of every day
+    html.push("<table>\n<tr><th>Day</th><th>Title</th><th>Part 1</th><th>Time</th><th>Part 2</th><th>Time</th><th>Input</th></tr>".to_owned());
+    for report in days {
+        let mut row = vec![format!("<a href=\"#day{}\">{}</a>", report.day, report.day), escape(title(report.day))];
+        for part in [1, 2] {
+            match report.parts.iter().find(|p| p.part == part) {
+                Some(part) => {
+                    row.push(answer_html(part));
+                    row.push(format_duration(&part.duration));
+                }
+                None => row.extend(["".to_owned(), "".to_owned()]),
+            }
+        }
+        row.push(format!("{} lines", report.input.lines));
+        html.push(format!("<tr><td>{}</td></tr>", row.join("</td><td>")));
+    }
+    html.push("</table>".to_owned());
+
+    // details of each day
+    for report in days {
+        html.push(format!("<section id=\"day{}\">\n<h2>Day {}: {}</h2>", report.day, report.day, escape(title(report.day))));
+        html.push("<ul>".to_owned());
+        for part in &report.parts {
+            html.push(format!("<li>Part {}: {} ({})</li>", part.part, answer_html(part), format_duration(&part.duration)));
+        }
+        let InputStats { bytes, lines, width } = report.input;
+        html.push(format!("<li>Input: {} bytes, {} lines, up to {} characters wide</li>", bytes, lines, width));
+        html.push("</ul>".to_owned());
+
+        if let Some(frame) = report.frame.as_ref().filter(|f| !f.rows.is_empty()) {
+            let cells = frame.rows.iter().map(|r| r.chars().count()).sum::<usize>();
+            match cells <= MAX_TEXT_CELLS {
+                true => html.push(frame_html(frame, theme)),
+                false => html.push(format!("<img class=\"frame\" alt=\"Day {} visualisation\" src=\"data:image/png;base64,{}\">",
+                                           report.day, base64(&frame_png(frame, theme)?))),
+            }
+        }
+        if let Some(drawing) = &report.drawing {
+            html.push(drawing.to_svg(DRAWING_SIZE));
+        }
+        html.push("</section>".to_owned());
+    }
+
+    html.push(SCRIPT.to_owned());
+    html.push("</body>\n</html>\n".to_owned());
+    Ok(html.join("\n"))
+}
+
+const DRAWING_SIZE: f64 = 600.0;
+/// Frames with more characters than this are shown as images, rather than as text
+const MAX_TEXT_CELLS: usize = 160 * 160;
+
+const STYLE: &str = "body { font-family: sans-serif; margin: 2em; } \
+    table { border-collapse: collapse; } \
+    th, td { border: 1px solid #ccc; padding: 0.3em 0.8em; text-align: left; } \
+    .correct { color: #2a2; } .incorrect, .error { color: #c22; } \
+    .about { color: #666; } \
+    img.frame { image-rendering: pixelated; max-width: 100%; } \
+    pre.frame { background: #141414; color: #c8c8c8; padding: 0.5em; line-height: 1; font-size: 8px; overflow: auto; }";
+
+/// Show the run time in the reader's own time zone
+const SCRIPT: &str = "<script>\
+    for (const t of document.querySelectorAll('.time')) \
+    t.textContent = new Date(t.dataset.timestamp * 1000).toLocaleString();\
+    </script>";
+
+fn title(day: u8) -> &'static str {
+    solutions::list().iter()
+        .find(|s| s.day == day)
+        .map(|s| s.title)
+        .unwrap_or("")
+}
+
+fn answer_html(part: &PartReport) -> String {
+    match (&part.answer, &part.check) {
+        (Err(e), _) => format!("<span class=\"error\">{}</span>", escape(e)),
+        (Ok(answer), Check::Correct) => format!("<span class=\"correct\">{} {}</span>", escape(&answer.to_string()), part.check.mark()),
+        (Ok(answer), Check::Incorrect(_)) => format!("<span class=\"incorrect\">{} {}</span>", escape(&answer.to_string()), escape(&part.check.mark())),
+        (Ok(answer), Check::Unknown) => escape(&answer.to_string()),
+    }
+}
+
+/// Show a frame as text, with runs of characters in the same style wrapped in styled spans
+fn frame_html(frame: &Frame, theme: Theme) -> String {
+    let mut html = String::from("<pre class=\"frame\">");
+    for row in 0..frame.rows.len() {
+        let mut runs: Vec<(String, Style)> = Vec::new();
+        for (c, style) in frame.cells(row) {
+            let style = theme.apply(style);
+            match runs.last_mut() {
+                Some((text, last)) if *last == style => text.push(c),
+                _ => runs.push((c.to_string(), style)),
+            }
+        }
+
+        for (text, style) in runs {
+            let mut css = Vec::new();
+            if let Some(fg) = style.fg { css.push(format!("color:{}", fg.hex())); }
+            if let Some(bg) = style.bg { css.push(format!("background:{}", bg.hex())); }
+            if style.bold { css.push("font-weight:bold".to_owned()); }
+            match css.is_empty() {
+                true => html.push_str(&escape(&text)),
+                false => html.push_str(&format!("<span style=\"{}\">{}</span>", css.join(";"), escape(&text))),
+            }
+        }
+        html.push('\n');
+    }
+    html.push_str("</pre>");
+    html
+}
+
+fn escape(text: &str) -> String {
+    text.replace('&', "&amp;").replace('<', "&lt;").replace('>', "&gt;").replace('"', "&quot;")
+}
+
+fn base64(data: &[u8]) -> String {
+    const ALPHABET: &[u8] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789+/";
+
+    let mut result = String::with_capacity(data.len().div_ceil(3) * 4);
+    for chunk in data.chunks(3) {
+        let bytes = [chunk[0], *chunk.get(1).unwrap_or(&0), *chunk.get(2).unwrap_or(&0)];
+        let n = (bytes[0] as u32) << 16 | (bytes[1] as u32) << 8 | bytes[2] as u32;
+        for i in 0..4 {
+            if i <= chunk.len() {
+                result.push(ALPHABET[(n >> (18 - 6 * i) & 0x3f) as usize] as char);
+            } else {
+                result.push('=');
+            }
+        }
+    }
+    result
+}
+
+// -------------------------------------------------------------------------------------------------
+// tests
+
+#[cfg(test)]
+mod tests {
+    use crate::visualise::Colour;
+
+    use super::*;
+
+    #[test]
+    fn encode_base64() {
+        assert_eq!("", base64(b""));
+        assert_eq!("Zg==", base64(b"f"));
+        assert_eq!("Zm8=", base64(b"fo"));
+        assert_eq!("Zm9v", base64(b"foo"));
+        assert_eq!("Zm9vYmFy", base64(b"foobar"));
+    }
+
+    #[test]
+    fn styled_frame_html() {
+        let red = Style::new().fg(Colour(255, 0, 0));
+        let frame = Frame::styled(vec!["<#.".to_owned(), "#".to_owned()], vec![
+            vec![red, red, Style::new()],
+            vec![Style::new().bg(Colour(0, 0, 255)).bold()],
+        ]);
+
+        assert_eq!("<pre class=\"frame\"><span style=\"color:#ff0000\">&lt;#</span>.\n\
+                    <span style=\"background:#0000ff;font-weight:bold\">#</span>\n</pre>",
+                   frame_html(&frame, Theme::Default));
+        assert_eq!("<pre class=\"frame\"><span style=\"color:#4c4c4c;font-weight:bold\">&lt;#</span>.\n\
+                    <span style=\"background:#1d1d1d;font-weight:bold\">#</span>\n</pre>",
+                   frame_html(&frame, Theme::Monochrome));
+    }
+
+    #[test]
+    fn input_stats() {
+        let stats = InputStats::of("#.#\n#\n.....\n");
+        assert_eq!((12, 3, 5), (stats.bytes, stats.lines, stats.width));
+    }
+}
@@ -0,0 +1,60 @@
+use std::time::{Duration, Instant};
+
+use crate::solutions::{Answer, Harness, SolveError};
+use crate::visualise::{Capture, Drawing, Frame, Visualiser};
+
+/// Outcome of running one part of a day's solution
+pub struct PartResult {
+    pub part: u8,
+    pub answer: Result<Answer, SolveError>,
+    pub duration: Duration,
+}
+
+/// Outcome of running the selected parts of a day's solution
+pub struct DayResult {
+    pub day: u8,
+    pub parts: Vec<PartResult>,
+}
+
+/// Run a single part of the given solution, timing how long it takes
+pub fn run_part(solution: &dyn Harness, day: u8, part: u8, input: &str, visualiser: &mut dyn Visualiser) -> PartResult {
+    let start = Instant::now();
+    let answer = match part {
+        1 => solution.part_1(input, visualiser),
+        _ => solution.part_2(input, visualiser),
+    };
+    let duration = start.elapsed();
+
+    PartResult { part, answer: answer.map_err(|e| e.in_part(day, part)), duration }
+}
+
+/// Run the selected parts of the given solution
+pub fn run_day(solution: &dyn Harness, day: u8, parts: &[u8], input: &str, visualiser: &mut dyn Visualiser) -> DayResult {
+    let parts = parts.iter()
+        .map(|&part| run_part(solution, day, part, input, visualiser))
+        .collect();
+
+    DayResult { day, parts }
+}
+
+/// Run the selected parts of the given solution again, untimed, to capture the last frame and
+/// drawing they show. The answers have already been reported, so they're ignored here.
+pub fn capture_day(solution: &dyn Harness, day: u8, parts: &[u8], input: &str) -> (Option<Frame>, Option<Drawing>) {
+    let mut capture = Capture::default();
+    for &part in parts {
+        let _ = run_part(solution, day, part, input, &mut capture);
+    }
+    capture.take()
+}
+
+/// Format a duration for display, using units appropriate to its size
+pub fn format_duration(duration: &Duration) -> String {
+    let micros = duration.as_micros();
+    if micros < 1000 {
+        format!("{}µs", micros)
+    } else if micros < 1_000_000 {
+        format!("{:.2}ms", micros as f64 / 1000.0)
+    } else {
+        format!("{:.2}s", duration.as_secs_f64())
+    }
+}
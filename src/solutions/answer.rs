@@ -0,0 +1,102 @@
+use std::convert::Infallible;
+use std::fmt::{Display, Formatter};
+use std::str::FromStr;
+
+/// The answer to one part of a day's challenge
+#[derive(Debug, Clone)]
+pub enum Answer {
+    Integer(i64),
+    /// for answers too large to fit in an `i64`
+    Unsigned(u128),
+    Text(String),
+}
+
+impl Display for Answer {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Answer::Integer(v) => write!(f, "{}", v),
+            Answer::Unsigned(v) => write!(f, "{}", v),
+            Answer::Text(v) => write!(f, "{}", v),
+        }
+    }
+}
+
+/// Answers are submitted as text, so two answers match if they'd be written the same way,
+/// regardless of which kind of answer they are
+impl PartialEq for Answer {
+    fn eq(&self, other: &Self) -> bool {
+        match (self, other) {
+            (Answer::Integer(a), Answer::Integer(b)) => a == b,
+            (Answer::Unsigned(a), Answer::Unsigned(b)) => a == b,
+            _ => self.to_string() == other.to_string(),
+        }
+    }
+}
+
+impl Eq for Answer {}
+
+/// Read an answer back from text, using the narrowest kind which fits
+impl FromStr for Answer {
+    type Err = Infallible;
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        let value = value.trim();
+        Ok(value.parse().map(Answer::Integer)
+            .or_else(|_| value.parse().map(Answer::Unsigned))
+            .unwrap_or_else(|_| Answer::Text(value.to_owned())))
+    }
+}
+
+impl From<i64> for Answer {
+    fn from(value: i64) -> Self {
+        Answer::Integer(value)
+    }
+}
+
+impl From<u64> for Answer {
+    fn from(value: u64) -> Self {
+        Answer::Unsigned(value as u128)
+    }
+}
+
+impl From<u128> for Answer {
+    fn from(value: u128) -> Self {
+        Answer::Unsigned(value)
+    }
+}
+
+impl From<String> for Answer {
+    fn from(value: String) -> Self {
+        Answer::Text(value)
+    }
+}
+
+impl From<&str> for Answer {
+    fn from(value: &str) -> Self {
+        Answer::Text(value.to_owned())
+    }
+}
+
+// -------------------------------------------------------------------------------------------------
+// tests
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_narrowest_kind() {
+        assert!(matches!("42".parse(), Ok(Answer::Integer(42))));
+        assert!(matches!("-42".parse(), Ok(Answer::Integer(-42))));
+        assert!(matches!("18446744073709551616".parse(), Ok(Answer::Unsigned(18446744073709551616))));
+        assert!(matches!("EGJHK".parse(), Ok(Answer::Text(t)) if t == "EGJHK"));
+    }
+
+    #[test]
+    fn compare_across_kinds() {
+        assert_eq!(Answer::Integer(42), Answer::Unsigned(42));
+        assert_eq!(Answer::Integer(42), Answer::Text("42".to_owned()));
+        assert_ne!(Answer::Integer(42), Answer::Integer(43));
+        assert_eq!("7,2,4", Answer::from("7,2,4").to_string());
+    }
+}
@@ -1,18 +1,23 @@
-use crate::solutions::Harness;
+use crate::solutions::{Answer, Harness, Solution, SolveError};
+use crate::visualise::Visualiser;
 
 pub struct Day1 {}
 
+pub const SOLUTION: Solution = Solution {
+    day: 1, title: "Trebuchet?!", parts: &[1, 2], new: || Box::new(Day1 {}),
+};
+
 impl Harness for Day1 {
-    fn part_1(&self, input: &str, _visualise: bool) -> i64 {
-        input.lines()
+    fn part_1(&self, input: &str, _visualiser: &mut dyn Visualiser) -> Result<Answer, SolveError> {
+        Ok(input.lines()
             .map(|line| extract_number(line, to_digit))
-            .sum()
+            .sum::<i64>().into())
     }
 
-    fn part_2(&self, input: &str, _visualise: bool) -> i64 {
-        input.lines()
+    fn part_2(&self, input: &str, _visualiser: &mut dyn Visualiser) -> Result<Answer, SolveError> {
+        Ok(input.lines()
             .map(|line| extract_number(line, to_digit_including_text))
-            .sum()
+            .sum::<i64>().into())
     }
 }
 
@@ -24,7 +29,7 @@ fn extract_number<F>(line: &str, to_digit: F) -> i64
     let mut first: char = ' ';
     let mut last: char = ' ';
 
-    for i in 0..line.len() {
+    for (i, _) in line.char_indices() {
         let x = to_digit(line, i);
         if x.is_some() {
             if first == ' ' {
@@ -44,7 +49,7 @@ fn extract_number<F>(line: &str, to_digit: F) -> i64
 }
 
 fn to_digit(line: &str, idx: usize) -> Option<char> {
-    let c = line.chars().nth(idx).unwrap();
+    let c = line[idx..].chars().next()?;
     if c.is_digit(10) {
         return Some(c);
     }
@@ -52,7 +57,7 @@ fn to_digit(line: &str, idx: usize) -> Option<char> {
 }
 
 fn to_digit_including_text(line: &str, idx: usize) -> Option<char> {
-    let c = line.chars().nth(idx).unwrap();
+    let c = line[idx..].chars().next()?;
     if c.is_digit(10) {
         return Some(c);
     }
@@ -1,32 +1,39 @@
-use std::collections::{HashSet, VecDeque};
+use std::collections::HashSet;
 
 use Direction::*;
 
-use crate::solutions::Harness;
-use crate::utils::grid::{Grid};
-use crate::utils::point::{Direction, Point};
+use crate::solutions::{Answer, Harness, Solution, SolveError};
+use crate::utils::grid::Grid;
+use crate::utils::point::{Connectivity, Direction, Point};
+use crate::utils::search::{flood_fill, grid_neighbours};
+use crate::visualise::{Colour, Style, Visualiser};
 
 pub struct Day10 {}
 
+pub const SOLUTION: Solution = Solution {
+    day: 10, title: "Pipe Maze", parts: &[1, 2], new: || Box::new(Day10 {}),
+};
+
 impl Harness for Day10 {
-    fn part_1(&self, input: &str, _visualise: bool) -> i64 {
-        let maze = parse_maze(input);
-        let start = maze.grid.find_first(|v| v.value == 'S').unwrap();
+    fn part_1(&self, input: &str, _visualiser: &mut dyn Visualiser) -> Result<Answer, SolveError> {
+        let maze = parse_maze(input)?;
+        let start = find_start(&maze)?;
 
         let path = follow_loop(&maze, &start);
-        (path.len() / 2) as i64
+        Ok(((path.len() / 2) as i64).into())
     }
 
-    fn part_2(&self, input: &str, visualise: bool) -> i64 {
-        let maze = parse_maze(input);
-        let start = maze.grid.find_first(|v| v.value == 'S').unwrap();
+    fn part_2(&self, input: &str, visualiser: &mut dyn Visualiser) -> Result<Answer, SolveError> {
+        let maze = parse_maze(input)?;
+        let start = find_start(&maze)?;
         let path = follow_loop(&maze, &start);
 
         let path_points = path.iter().map(|p| p.clone()).collect::<HashSet<_>>();
 
         let mut right = right_side_of(&path, &maze);
         right.retain(|p| !path_points.contains(p));
-        flood(&mut right, &path_points, &maze);
+        // spread out from the right side, up to the path
+        let right = flood_fill(right, grid_neighbours(&maze.grid, Connectivity::Four, |p, _| !path_points.contains(p)));
 
         let bounds = outer_bounds(&path);
         let inner = if is_outer_region(&right, &bounds) {
@@ -37,14 +44,23 @@ impl Harness for Day10 {
             right
         };
 
-        if visualise { _visualise(&maze, &path_points, &inner); }
+        if visualiser.enabled() { _visualise(visualiser, &maze, &path_points, &inner); }
+
+        Ok((inner.len() as i64).into())
+    }
 
-        inner.len() as i64
+    fn parse(&self, input: &str) -> Option<Result<(), SolveError>> {
+        Some(parse_maze(input).map(|_| ()))
     }
 }
 
 // ----------------
 
+fn find_start(maze: &Maze) -> Result<Point, SolveError> {
+    maze.grid.find_first(|v| v.value == 'S')
+        .ok_or_else(|| SolveError::new("No start position 'S' found"))
+}
+
 /// Follow the loop within the maze, from the given start point
 fn follow_loop(maze: &Maze, start: &Point) -> Vec<Point> {
     let mut path = Vec::new();
@@ -95,23 +111,6 @@ fn is_outer_region(region: &HashSet<Point>, bounds: &(Point, Point)) -> bool {
     false
 }
 
-fn flood(region: &mut HashSet<Point>, path: &HashSet<Point>, maze: &Maze) {
-    let mut visited: HashSet<Point> = HashSet::new();
-    let mut queue = region.iter().map(|p| p.clone())
-        .collect::<VecDeque<_>>();
-
-    while let Some(point) = queue.pop_front() {
-        // find adjacent points
-        let mut adjacent = maze.grid.adjacent(&point);
-        // remove any we already processed
-        adjacent.retain(|p| !(region.contains(&p) || visited.contains(p) || path.contains(p)));
-
-        for p in &adjacent { region.insert(p.clone()); }
-        visited.insert(point);
-        queue.extend(adjacent);
-    }
-}
-
 /// Find all the points immediately to the right of the given path
 fn right_side_of(path: &Vec<Point>, maze: &Maze) -> HashSet<Point> {
     let mut right = HashSet::new();
@@ -129,25 +128,19 @@ fn right_side_of(path: &Vec<Point>, maze: &Maze) -> HashSet<Point> {
         let dirs = match (maze.value(point), dir) {
             ('|', North | South) => vec![dir.rotate_clockwise()],
             ('─', East | West) => vec![dir.rotate_clockwise()],
-            ('┐', North) => vec![North, East],
-            ('┐', East) => vec![South, West],
-            ('┘', South) => vec![North, West],
-            ('┘', East) => vec![South, East],
-            ('┌', North) => vec![South, East],
-            ('┌', West) => vec![North, West],
-            ('└', South) => vec![South, West],
-            ('└', East) => vec![North, East],
+            ('┐', North) => vec![North, NorthEast, East],
+            ('┐', East) => vec![South, SouthWest, West],
+            ('┘', South) => vec![North, NorthWest, West],
+            ('┘', East) => vec![South, SouthEast, East],
+            ('┌', North) => vec![South, SouthEast, East],
+            ('┌', West) => vec![North, NorthWest, West],
+            ('└', South) => vec![South, SouthWest, West],
+            ('└', East) => vec![North, NorthEast, East],
             _ => vec![]
         };
 
-        match dirs[..] {
-            [d] => { push(maze.grid.travel(point, d)); }
-            [d1, d2] => {
-                push(maze.grid.travel(point, d1));
-                push(maze.grid.travel(point, d2));
-                push(maze.grid.travel_diag(point, d1, d2));
-            }
-            _ => {}
+        for d in dirs {
+            push(maze.grid.travel(point, d));
         }
     }
 
@@ -171,10 +164,6 @@ struct Maze {
 }
 
 impl Maze {
-    fn new(adjacency: Vec<Vec<MazePoint>>) -> Maze {
-        Maze { grid: Grid::<MazePoint>::new(adjacency) }
-    }
-
     fn follow(&self, p: &Point, direction: Direction) -> Option<Point> {
         let moved = self.grid.travel(p, direction);
         moved.filter(|n| self.choices(n).contains(&direction.invert()))
@@ -200,41 +189,45 @@ impl Maze {
 // -------------------------------------------------------------------------------------------------
 // parsing
 
-fn parse_maze(input: &str) -> Maze {
-    let adjacent = input.lines()
-        .map(|line| parse_line(line))
-        .collect();
-
-    Maze::new(adjacent)
+fn parse_maze(input: &str) -> Result<Maze, SolveError> {
+    Ok(Maze { grid: Grid::parse(input, |c, _| parse_pipe(c))? })
 }
 
-fn parse_line(line: &str) -> Vec<MazePoint> {
-    let mut result = Vec::new();
-
-    for c in line.chars() {
-        let (adjacent, value) = match c {
-            '.' => (vec![], c),
-            '|' => (vec![North, South], '│'),
-            '-' => (vec![East, West], '─'),
-            'L' => (vec![North, East], '└'),
-            'J' => (vec![North, West], '┘'),
-            '7' => (vec![South, West], '┐'),
-            'F' => (vec![East, South], '┌'),
-            'S' => (vec![North, East, South, West], 'S'),
-            _ => panic!("Unknown symbol: {}", c)
-        };
-        result.push(MazePoint { adjacent, value });
-    }
+fn parse_pipe(c: char) -> Result<MazePoint, SolveError> {
+    let (adjacent, value) = match c {
+        '.' => (vec![], c),
+        '|' => (vec![North, South], '│'),
+        '-' => (vec![East, West], '─'),
+        'L' => (vec![North, East], '└'),
+        'J' => (vec![North, West], '┘'),
+        '7' => (vec![South, West], '┐'),
+        'F' => (vec![East, South], '┌'),
+        'S' => (vec![North, East, South, West], 'S'),
+        _ => return Err(SolveError::new(format!("Unknown symbol '{}'", c)))
+    };
 
-    result
+    Ok(MazePoint { adjacent, value })
 }
 
 // -------------------------------------------------------------------------------------------------
 // visualisation
 
-fn _visualise(maze: &Maze, path: &HashSet<Point>, inside: &HashSet<Point>) {
-    maze.grid.visualise(|v, p| {
-        if path.contains(p) { v.value.to_string() } else if inside.contains(p) { "@".to_owned() } else { ".".to_owned() }
+const PIPE: Colour = Colour(90, 160, 255);
+const START: Colour = Colour(255, 80, 80);
+const INSIDE: Colour = Colour(40, 110, 40);
+
+/// Show the loop of pipes in bold, with the start picked out and the tiles inside it filled in
+fn _visualise(visualiser: &mut dyn Visualiser, maze: &Maze, path: &HashSet<Point>, inside: &HashSet<Point>) {
+    let frame = maze.grid.visualise_styled(|v, p| {
+        if v.value == 'S' {
+            (v.value.to_string(), Style::new().fg(START).bold())
+        } else if path.contains(p) {
+            (v.value.to_string(), Style::new().fg(PIPE).bold())
+        } else if inside.contains(p) {
+            ("@".to_owned(), Style::new().fg(Colour(120, 230, 120)).bg(INSIDE))
+        } else {
+            (".".to_owned(), Style::new())
+        }
     });
-    println!();
+    visualiser.frame(&frame);
 }
\ No newline at end of file
@@ -1,29 +1,34 @@
 use std::collections::BTreeSet;
 use std::iter::zip;
 
-use crate::solutions::Harness;
+use crate::solutions::{Answer, Harness, Solution, SolveError};
+use crate::visualise::{Colour, Frame, Style, Visualiser};
 
 pub struct Day11 {}
 
+pub const SOLUTION: Solution = Solution {
+    day: 11, title: "Cosmic Expansion", parts: &[1, 2], new: || Box::new(Day11 {}),
+};
+
 impl Harness for Day11 {
-    fn part_1(&self, input: &str, visualise: bool) -> i64 {
+    fn part_1(&self, input: &str, visualiser: &mut dyn Visualiser) -> Result<Answer, SolveError> {
         // parse the data
         let raw = parse_universe(input);
-        if visualise { _visualise(&raw); }
+        if visualiser.enabled() { _visualise(visualiser, &raw); }
         // expand the universe
         let universe = expand_universe(&raw, 2);
-        if visualise { _visualise(&universe); }
+        if visualiser.enabled() { _visualise(visualiser, &universe); }
         // sum the paths between galaxies
-        sum_paths(&universe.galaxies)
+        Ok(sum_paths(&universe.galaxies).into())
     }
 
-    fn part_2(&self, input: &str, _visualise: bool) -> i64 {
+    fn part_2(&self, input: &str, _visualiser: &mut dyn Visualiser) -> Result<Answer, SolveError> {
         // parse the data
         let raw = parse_universe(input);
         // expand the universe
         let universe = expand_universe(&raw, 1000000);
         // sum the paths between galaxies
-        sum_paths(&universe.galaxies)
+        Ok(sum_paths(&universe.galaxies).into())
     }
 }
 
@@ -113,13 +118,17 @@ fn parse_universe(data: &str) -> Universe {
 // -------------------------------------------------------------------------------------------------
 // visualisation
 
-fn _visualise(universe: &Universe) {
-    for y in 0..universe.height {
-        for x in 0..universe.width {
-            let p = Galaxy { x, y };
-            if universe.galaxies.contains(&p) { print!("#"); } else { print!("."); }
-        }
-        println!();
-    }
-    println!();
+const GALAXY: Colour = Colour(255, 230, 120);
+
+/// Show the galaxies in bold, against dim empty space
+fn _visualise(visualiser: &mut dyn Visualiser, universe: &Universe) {
+    let galaxy = Style::new().fg(GALAXY).bold();
+    let space = Style::new().fg(Colour(70, 70, 90));
+
+    let (rows, styles) = (0..universe.height)
+        .map(|y| (0..universe.width)
+            .map(|x| if universe.galaxies.contains(&Galaxy { x, y }) { ('#', galaxy) } else { ('.', space) })
+            .unzip())
+        .unzip();
+    visualiser.frame(&Frame::styled(rows, styles));
 }
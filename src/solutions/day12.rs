@@ -3,33 +3,41 @@ use std::fmt::{Debug, Formatter};
 
 use Spring::*;
 
-use crate::solutions::Harness;
+use crate::solutions::{Answer, Harness, Solution, SolveError};
+use crate::solutions::error::{parse_lines, parse_number, split_once};
+use crate::visualise::Visualiser;
 
 pub struct Day12 {}
 
+pub const SOLUTION: Solution = Solution {
+    day: 12, title: "Hot Springs", parts: &[1, 2], new: || Box::new(Day12 {}),
+};
+
 impl Harness for Day12 {
-    fn part_1(&self, input: &str, _visualise: bool) -> i64 {
+    fn part_1(&self, input: &str, _visualiser: &mut dyn Visualiser) -> Result<Answer, SolveError> {
         let mut memo = HashMap::new();
 
-        input.lines()
-            .map(|line| {
-                let (springs, rules) = parse_record(&line);
+        Ok(parse_lines(input, parse_record)?.into_iter()
+            .map(|(springs, rules)| {
                 // brute_force_count(&springs, &rules) // slow!
                 count(&mut memo, &springs, &rules)
             })
-            .sum()
+            .sum::<i64>().into())
     }
 
-    fn part_2(&self, input: &str, _visualise: bool) -> i64 {
+    fn part_2(&self, input: &str, _visualiser: &mut dyn Visualiser) -> Result<Answer, SolveError> {
         let mut memo = HashMap::new();
 
-        input.lines()
-            .map(|line| {
-                let (springs, rules) = parse_record(&line);
+        Ok(parse_lines(input, parse_record)?.into_iter()
+            .map(|(springs, rules)| {
                 let (springs, rules) = uncoil(springs, rules);
                 count(&mut memo, &springs, &rules)
             })
-            .sum()
+            .sum::<i64>().into())
+    }
+
+    fn parse(&self, input: &str) -> Option<Result<(), SolveError>> {
+        Some(parse_lines(input, parse_record).map(|_| ()))
     }
 }
 
@@ -181,19 +189,19 @@ impl Debug for Spring {
 // -------------------------------------------------------------------------------------------------
 // parsing
 
-fn parse_record(line: &str) -> (Vec<Spring>, Vec<usize>) {
-    let (springs, hints) = line.split_once(" ").unwrap();
+fn parse_record(line: &str) -> Result<(Vec<Spring>, Vec<usize>), SolveError> {
+    let (springs, hints) = split_once(line, " ")?;
 
     let springs = springs.chars().map(|c| match c {
-        '.' => Good,
-        '#' => Bad,
-        '?' => Unknown,
-        _ => panic!("Unknown symbol: {}", c)
-    }).collect();
+        '.' => Ok(Good),
+        '#' => Ok(Bad),
+        '?' => Ok(Unknown),
+        _ => Err(SolveError::new(format!("Unknown symbol: '{}'", c)))
+    }).collect::<Result<_, _>>()?;
 
-    let hints = hints.split(",").map(|n| n.parse().unwrap()).collect();
+    let hints = hints.split(",").map(parse_number).collect::<Result<_, _>>()?;
 
-    (springs, hints)
+    Ok((springs, hints))
 }
 
 // ---------------------------------------------------------------------------------------------------------------------
@@ -205,7 +213,7 @@ mod tests {
 
     fn _check(record: &str, expect: i64) {
         let mut m = HashMap::new();
-        let (s, r) = parse_record(record);
+        let (s, r) = parse_record(record).unwrap();
         assert_eq!(count(&mut m, &s, &r), expect, "`{}` should be {}", record, expect);
     }
 
@@ -216,7 +224,7 @@ mod tests {
 
     fn _check_expanded(record: &str, expect: i64) {
         let mut m = HashMap::new();
-        let (s, r) = parse_record(record);
+        let (s, r) = parse_record(record).unwrap();
         let (s, r) = uncoil(s, r);
         assert_eq!(count(&mut m, &s, &r), expect, "`{}` should be {}", record, expect);
     }
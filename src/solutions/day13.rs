@@ -1,27 +1,36 @@
 use std::usize;
 
-use crate::solutions::Harness;
+use crate::solutions::{Answer, Harness, Solution, SolveError};
+use crate::visualise::{Frame, Visualiser};
 
 pub struct Day13 {}
 
+pub const SOLUTION: Solution = Solution {
+    day: 13, title: "Point of Incidence", parts: &[1, 2], new: || Box::new(Day13 {}),
+};
+
 impl Harness for Day13 {
-    fn part_1(&self, input: &str, _visualise: bool) -> i64 {
-        parse_file(input).iter()
+    fn part_1(&self, input: &str, _visualiser: &mut dyn Visualiser) -> Result<Answer, SolveError> {
+        Ok(parse_file(input)?.iter()
             .map(|pattern| {
-                let symmetry = find_symmetry(&pattern);
+                let symmetry = find_symmetry(pattern);
                 score(&symmetry)
             })
-            .sum()
+            .sum::<i64>().into())
     }
 
-    fn part_2(&self, input: &str, _visualise: bool) -> i64 {
-        parse_file(input).iter()
+    fn part_2(&self, input: &str, _visualiser: &mut dyn Visualiser) -> Result<Answer, SolveError> {
+        Ok(parse_file(input)?.iter()
             .map(|pattern| {
-                let original = find_symmetry(&pattern);
-                let symmetry = find_smudged_symmetry(&pattern, &original);
+                let original = find_symmetry(pattern);
+                let symmetry = find_smudged_symmetry(pattern, &original);
                 score(&symmetry)
             })
-            .sum()
+            .sum::<i64>().into())
+    }
+
+    fn parse(&self, input: &str) -> Option<Result<(), SolveError>> {
+        Some(parse_file(input).map(|_| ()))
     }
 }
 
@@ -126,28 +135,35 @@ type Symmetry = (Option<usize>, Option<usize>);
 // -------------------------------------------------------------------------------------------------
 // parsing
 
-fn parse_file(file: &str) -> Vec<Pattern> {
+fn parse_file(file: &str) -> Result<Vec<Pattern<'_>>, SolveError> {
     let mut result = Vec::new();
 
     let mut pattern = Pattern::new();
-    for line in file.lines() {
+    for (i, line) in file.lines().enumerate() {
         if line.is_empty() {
-            result.push(pattern);
+            if !pattern.is_empty() { result.push(pattern); }
             pattern = Pattern::new();
-        } else {
-            pattern.push(line);
+            continue;
+        }
+
+        // patterns must be rectangular, and only contain ash and rocks
+        if let Some(c) = line.chars().find(|c| *c != '.' && *c != '#') {
+            return Err(SolveError::new(format!("Unknown symbol: '{}'", c)).on_line(i + 1));
+        }
+        if pattern.first().is_some_and(|first| first.len() != line.len()) {
+            return Err(SolveError::new("Pattern rows should all be the same width").on_line(i + 1));
         }
+        pattern.push(line);
     }
-    if pattern.len() > 0 { result.push(pattern); }
+    if !pattern.is_empty() { result.push(pattern); }
 
-    result
+    Ok(result)
 }
 
 // -------------------------------------------------------------------------------------------------
 // visualisation
 
-fn _visualise<R: AsRef<Row>>(pattern: &[R]) {
-    for row in pattern {
-        println!("{}", row.as_ref());
-    }
+fn _visualise<R: AsRef<Row>>(visualiser: &mut dyn Visualiser, pattern: &[R]) {
+    let rows = pattern.iter().map(|row| row.as_ref().to_string()).collect();
+    visualiser.frame(&Frame::new(rows));
 }
\ No newline at end of file
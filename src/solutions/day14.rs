@@ -1,26 +1,31 @@
 use std::collections::HashMap;
 
-use crate::solutions::Harness;
+use crate::solutions::{Answer, Harness, Solution, SolveError};
 use crate::utils::grid::Grid;
 use crate::utils::point::{Direction, Point};
 use crate::utils::point::Direction::*;
+use crate::visualise::{Colour, Style, Visualiser};
 
 pub struct Day14 {}
 
+pub const SOLUTION: Solution = Solution {
+    day: 14, title: "Parabolic Reflector Dish", parts: &[1, 2], new: || Box::new(Day14 {}),
+};
+
 impl Harness for Day14 {
-    fn part_1(&self, input: &str, visualise: bool) -> i64 {
-        let mut dish = parse_dish(input);
-        if visualise { _visualise(&dish); }
+    fn part_1(&self, input: &str, visualiser: &mut dyn Visualiser) -> Result<Answer, SolveError> {
+        let mut dish = parse_dish(input)?;
+        if visualiser.enabled() { _visualise(visualiser, &dish); }
 
         tilt_north(&mut dish);
-        if visualise { _visualise(&dish); }
+        if visualiser.enabled() { _visualise(visualiser, &dish); }
 
-        calc_load(&dish)
+        Ok(calc_load(&dish).into())
     }
 
-    fn part_2(&self, input: &str, visualise: bool) -> i64 {
-        let mut dish = parse_dish(input);
-        if visualise { _visualise(&dish); }
+    fn part_2(&self, input: &str, visualiser: &mut dyn Visualiser) -> Result<Answer, SolveError> {
+        let mut dish = parse_dish(input)?;
+        if visualiser.enabled() { _visualise(visualiser, &dish); }
 
         // track seen rock positions until we find a repeating pattern
         let mut seen = HashMap::new();
@@ -36,9 +41,9 @@ impl Harness for Day14 {
             tilt_east(&mut dish);
             count += 1;
 
-            if visualise {
-                println!("Cycle {}:", count);
-                _visualise(&dish);
+            if visualiser.enabled() {
+                visualiser.event(&format!("Cycle {}:", count));
+                _visualise(visualiser, &dish);
             }
 
             // grab the position of the rocks after this cycle
@@ -53,7 +58,11 @@ impl Harness for Day14 {
             }
         }
 
-        calc_load(&dish)
+        Ok(calc_load(&dish).into())
+    }
+
+    fn parse(&self, input: &str) -> Option<Result<(), SolveError>> {
+        Some(parse_dish(input).map(|_| ()))
     }
 }
 
@@ -125,18 +134,57 @@ fn is_rock(value: &char) -> bool {
 // -------------------------------------------------------------------------------------------------
 // parsing
 
-fn parse_dish(input: &str) -> Dish {
-    let cells: Vec<Vec<char>> = input.lines()
-        .map(|line| line.chars().collect())
-        .collect();
-
-    Dish::new(cells)
+fn parse_dish(input: &str) -> Result<Dish, SolveError> {
+    Ok(Dish::parse(input, |c, _| match c {
+        EMPTY | ROCK | '#' => Ok(c),
+        _ => Err(SolveError::new(format!("Unknown symbol '{}'", c))),
+    })?)
 }
 
 // -------------------------------------------------------------------------------------------------
 // visualisation
 
-fn _visualise(dish: &Dish) {
-    dish.visualise(|v, _| v.to_string());
-    println!();
-}
\ No newline at end of file
+/// Show the round rocks in bold, and the cube rocks as solid blocks
+fn _visualise(visualiser: &mut dyn Visualiser, dish: &Dish) {
+    let frame = dish.visualise_styled(|v, _| {
+        let style = match v {
+            'O' => Style::new().fg(Colour(230, 230, 230)).bold(),
+            '#' => Style::new().fg(Colour(110, 110, 110)).bg(Colour(60, 60, 60)),
+            _ => Style::new(),
+        };
+        (v.to_string(), style)
+    });
+    visualiser.frame(&frame);
+}
+
+// -------------------------------------------------------------------------------------------------
+// tests
+
+#[cfg(test)]
+mod tests {
+    use crate::visualise::Recorder;
+
+    use super::*;
+
+    const EXAMPLE: &str = "O....#....
+O.OO#....#
+.....##...
+OO.#O....O
+.O.....O#.
+O.#..O.#.#
+..O..#O..O
+.......O..
+#....###..
+#OO..#....";
+
+    #[test]
+    fn visualise_tilt() {
+        let mut recorder = Recorder::default();
+        assert_eq!(Ok(Answer::Integer(136)), Day14 {}.part_1(EXAMPLE, &mut recorder));
+
+        // before and after tilting north
+        assert_eq!(2, recorder.frames.len());
+        assert_eq!(EXAMPLE.lines().collect::<Vec<_>>(), recorder.frames[0].rows);
+        assert_eq!("OOOO.#.O..", recorder.frames[1].rows[0]);
+    }
+}
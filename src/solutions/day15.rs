@@ -1,30 +1,36 @@
 use std::fmt::{Debug, Formatter};
 
-use crate::solutions::Harness;
+use crate::solutions::{Answer, Harness, Solution, SolveError};
+use crate::solutions::error::parse_number;
+use crate::visualise::{Frame, Visualiser};
 
 pub struct Day15 {}
 
+pub const SOLUTION: Solution = Solution {
+    day: 15, title: "Lens Library", parts: &[1, 2], new: || Box::new(Day15 {}),
+};
+
 impl Harness for Day15 {
-    fn part_1(&self, input: &str, _visualise: bool) -> i64 {
-        input.split(",")
-            .map(|s| hash(s))
-            .sum()
+    fn part_1(&self, input: &str, _visualiser: &mut dyn Visualiser) -> Result<Answer, SolveError> {
+        Ok(input.trim_end().split(",")
+            .map(hash)
+            .sum::<i64>().into())
     }
 
-    fn part_2(&self, input: &str, visualise: bool) -> i64 {
-        let steps: Vec<Lens> = parse_steps(input);
+    fn part_2(&self, input: &str, visualiser: &mut dyn Visualiser) -> Result<Answer, SolveError> {
+        let steps: Vec<Lens> = parse_steps(input.trim_end())?;
 
         let mut boxes = vec![vec![]; 256];
         for step in steps {
             let hash = step.hash() as usize;
-            if visualise { println!("\n{:?}", step); }
+            if visualiser.enabled() { visualiser.event(&format!("\n{:?}", step)); }
             apply_step(step, &mut boxes[hash]);
-            if visualise { _visualise(&boxes) };
+            if visualiser.enabled() { _visualise(visualiser, &boxes) };
         }
 
-        boxes.iter().enumerate()
+        Ok(boxes.iter().enumerate()
             .map(|(i, b)| calc_box(i as i64, b))
-            .sum()
+            .sum::<i64>().into())
     }
 }
 
@@ -50,13 +56,14 @@ fn calc_box(num: i64, boxx: &[Lens]) -> i64 {
         .sum()
 }
 
-fn parse_steps(input: &str) -> Vec<Lens> {
+fn parse_steps(input: &str) -> Result<Vec<Lens<'_>>, SolveError> {
     input.split(",")
-        .map(|s| if s.contains("=") {
-            let (l, f) = s.split_once("=").unwrap();
-            Lens::new(l, f.parse().unwrap())
+        .map(|s| if let Some((l, f)) = s.split_once("=") {
+            Ok(Lens::new(l, parse_number(f)?))
+        } else if let Some(l) = s.strip_suffix("-") {
+            Ok(Lens::new(l, 0))
         } else {
-            Lens::new(&s[..s.len() - 1], 0)
+            Err(SolveError::new(format!("Invalid step: '{}'", s)))
         })
         .collect()
 }
@@ -100,12 +107,12 @@ impl Debug for Lens<'_> {
 // -------------------------------------------------------------------------------------------------
 // visualisation
 
-fn _visualise(boxes: &[Vec<Lens>]) {
-    for (i, b) in boxes.iter().enumerate() {
-        if !b.is_empty() {
-            println!("Box {}: {:?}", i, b);
-        }
-    }
+fn _visualise(visualiser: &mut dyn Visualiser, boxes: &[Vec<Lens>]) {
+    let rows = boxes.iter().enumerate()
+        .filter(|(_, b)| !b.is_empty())
+        .map(|(i, b)| format!("Box {}: {:?}", i, b))
+        .collect();
+    visualiser.frame(&Frame::new(rows));
 }
 
 
@@ -1,61 +1,59 @@
-use std::collections::{HashMap, HashSet, VecDeque};
+use std::collections::HashSet;
 use std::fmt::{Debug, Formatter};
 use std::vec;
 
 use Direction::*;
 
-use crate::solutions::Harness;
+use crate::solutions::{Answer, Harness, Solution, SolveError};
 use crate::utils::grid::Grid;
 use crate::utils::point::{Direction, Point};
+use crate::utils::search::flood_fill;
+use crate::visualise::{Colour, Style, Visualiser};
 
 pub struct Day16 {}
 
+pub const SOLUTION: Solution = Solution {
+    day: 16, title: "The Floor Will Be Lava", parts: &[1, 2], new: || Box::new(Day16 {}),
+};
+
 impl Harness for Day16 {
-    fn part_1(&self, input: &str, visualise: bool) -> i64 {
-        let contraption = parse_contraption(input);
+    fn part_1(&self, input: &str, visualiser: &mut dyn Visualiser) -> Result<Answer, SolveError> {
+        let contraption = parse_contraption(input)?;
         let beam = Beam::new(Point::new(0, 0), East);
-        energise(&contraption, &beam, visualise)
+        Ok(energise(&contraption, &beam, visualiser).into())
     }
 
-    fn part_2(&self, input: &str, visualise: bool) -> i64 {
-        let contraption = parse_contraption(input);
+    fn part_2(&self, input: &str, visualiser: &mut dyn Visualiser) -> Result<Answer, SolveError> {
+        let contraption = parse_contraption(input)?;
 
         let mut max = 0;
         for p in contraption.grid.points() {
             for d in contraption.start_options(&p) {
-                let e = energise(&contraption, &Beam::new(p, d), visualise);
+                let e = energise(&contraption, &Beam::new(p, d), visualiser);
                 if e > max { max = e; }
             }
         }
-        max
+        Ok(max.into())
+    }
+
+    fn parse(&self, input: &str) -> Option<Result<(), SolveError>> {
+        Some(parse_contraption(input).map(|_| ()))
     }
 }
 
 // ----------------
 
-fn energise(contraption: &Contraption, beam: &Beam, visualise: bool) -> i64 {
-    // track which cells are energised
-    let mut energy: HashMap<Point, i64> = HashMap::new();
-    // make sure we don't get stuck in loops
-    let mut processed = HashSet::new();
-
-    // all the beams currently in play
-    let mut beams = VecDeque::new();
-    // (initial beam)
-    beams.push_front(beam.clone());
-
-    while let Some(beam) = beams.pop_front() {
-        if processed.contains(&beam) { continue; }
+fn energise(contraption: &Contraption, beam: &Beam, visualiser: &mut dyn Visualiser) -> i64 {
+    // follow the beam through the contraption, each position and direction only once so that
+    // loops come to an end
+    let beams = flood_fill([beam.clone()], |b| contraption.next(b));
 
-        energy.entry(beam.pos)
-            .and_modify(|e| *e += 1)
-            .or_insert(0);
-
-        beams.extend(contraption.next(&beam));
-        processed.insert(beam);
-    }
+    // track which cells are energised
+    let energy = beams.into_iter()
+        .map(|b| b.pos)
+        .collect::<HashSet<_>>();
 
-    if visualise { _visualise(&contraption, &energy); }
+    if visualiser.enabled() { _visualise(visualiser, contraption, &energy); }
 
     energy.len() as i64
 }
@@ -81,7 +79,11 @@ impl Debug for Beam {
             North => '^',
             South => 'V',
             West => '<',
-            East => '>'
+            East => '>',
+            NorthEast => '↗',
+            SouthEast => '↘',
+            SouthWest => '↙',
+            NorthWest => '↖',
         })
     }
 }
@@ -96,27 +98,25 @@ impl Contraption {
         let v = self.grid.get(&beam.pos);
         if v.is_none() { return vec![]; }
 
+        // beams only ever travel north, south, east or west
+        let vertical = matches!(beam.dir, North | South);
         let dirs = match v.unwrap() {
             // continue
             '.' => vec![beam.dir],
             // split
-            '|' => match beam.dir {
-                North | South => vec![beam.dir],
-                East | West => vec![North, South]
-            },
-            '-' => match beam.dir {
-                North | South => vec![West, East],
-                East | West => vec![beam.dir],
-            },
+            '|' => if vertical { vec![beam.dir] } else { vec![North, South] },
+            '-' => if vertical { vec![West, East] } else { vec![beam.dir] },
             // bounce
-            '/' => match beam.dir {
-                North | South => vec![beam.dir.rotate_clockwise()],
-                East | West => vec![beam.dir.rotate_anticlockwise()],
+            '/' => if vertical {
+                vec![beam.dir.rotate_clockwise()]
+            } else {
+                vec![beam.dir.rotate_anticlockwise()]
+            },
+            '\\' => if vertical {
+                vec![beam.dir.rotate_anticlockwise()]
+            } else {
+                vec![beam.dir.rotate_clockwise()]
             },
-            '\\' => match beam.dir {
-                North | South => vec![beam.dir.rotate_anticlockwise()],
-                East | West => vec![beam.dir.rotate_clockwise()],
-            }
             _ => vec![]
         };
 
@@ -140,19 +140,29 @@ impl Contraption {
 // -------------------------------------------------------------------------------------------------
 // parsing
 
-fn parse_contraption(input: &str) -> Contraption {
-    let cells: Vec<Vec<char>> = input.lines()
-        .map(|line| line.chars().collect())
-        .collect();
+fn parse_contraption(input: &str) -> Result<Contraption, SolveError> {
+    let grid = Grid::parse(input, |c, _| match c {
+        '.' | '/' | '\\' | '|' | '-' => Ok(c),
+        _ => Err(SolveError::new(format!("Unknown symbol '{}'", c))),
+    })?;
 
-    Contraption { grid: Grid::<char>::new(cells) }
+    Ok(Contraption { grid })
 }
 
 // -------------------------------------------------------------------------------------------------
 // visualisation
 
-fn _visualise(contraption: &Contraption, energy: &HashMap<Point, i64>) {
-    contraption.grid.visualise(|_, p| {
-        if energy.contains_key(&p) { "#" } else { "." }
+const BEAM: Colour = Colour(255, 210, 60);
+const ENERGISED: Colour = Colour(120, 70, 0);
+
+/// Show the energised tiles lit up, with the mirrors and splitters drawn over them
+fn _visualise(visualiser: &mut dyn Visualiser, contraption: &Contraption, energy: &HashSet<Point>) {
+    let frame = contraption.grid.visualise_styled(|v, p| {
+        match (energy.contains(p), v) {
+            (true, '.') => ("#".to_owned(), Style::new().fg(BEAM).bg(ENERGISED)),
+            (true, _) => (v.to_string(), Style::new().fg(BEAM).bg(ENERGISED).bold()),
+            (false, _) => (v.to_string(), Style::new().fg(Colour(110, 110, 110))),
+        }
     });
+    visualiser.frame(&frame);
 }
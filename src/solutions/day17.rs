@@ -1,32 +1,50 @@
-use std::cmp::Ordering;
-use std::collections::{BinaryHeap, HashSet};
+use std::collections::HashMap;
 
-use crate::solutions::Harness;
+use crate::solutions::{Answer, Harness, Solution, SolveError};
 use crate::utils::grid::Grid;
+use crate::utils::path::Path;
 use crate::utils::point::{Direction, Point};
 use crate::utils::point::Direction::*;
+use crate::utils::search::dijkstra;
+use crate::visualise::{Colour, Style, Visualiser};
 
 pub struct Day17 {}
 
+pub const SOLUTION: Solution = Solution {
+    day: 17, title: "Clumsy Crucible", parts: &[1, 2], new: || Box::new(Day17 {}),
+};
+
 impl Harness for Day17 {
-    fn part_1(&self, input: &str, _visualise: bool) -> i64 {
-        let city = parse_city(input);
+    fn part_1(&self, input: &str, visualiser: &mut dyn Visualiser) -> Result<Answer, SolveError> {
+        let city = parse_city(input)?;
 
         // starting in top left
         let start = Point::new(0, 0);
         // aiming for bottom right
         let target = Point::new(city.width() - 1, city.height() - 1);
 
-        search(&city, start, target, 0, 3)
+        let (heat_loss, route) = search(&city, start, target, 0, 3)
+            .ok_or_else(|| SolveError::new("No route found"))?;
+        if visualiser.enabled() { _visualise(visualiser, &city, &route); }
+
+        Ok(heat_loss.into())
     }
 
-    fn part_2(&self, input: &str, _visualise: bool) -> i64 {
-        let city = parse_city(input);
+    fn part_2(&self, input: &str, visualiser: &mut dyn Visualiser) -> Result<Answer, SolveError> {
+        let city = parse_city(input)?;
 
         let start = Point::new(0, 0);
         let target = Point::new(city.width() - 1, city.height() - 1);
 
-        search(&city, start, target, 4, 10)
+        let (heat_loss, route) = search(&city, start, target, 4, 10)
+            .ok_or_else(|| SolveError::new("No route found"))?;
+        if visualiser.enabled() { _visualise(visualiser, &city, &route); }
+
+        Ok(heat_loss.into())
+    }
+
+    fn parse(&self, input: &str) -> Option<Result<(), SolveError>> {
+        Some(parse_city(input).map(|_| ()))
     }
 }
 
@@ -35,60 +53,55 @@ impl Harness for Day17 {
 /// Dijkstra search, modified to handle the constraints:
 ///     1. min and/or max steps per direction
 ///     2. can only go forward, left, or right from each position
-fn search(city: &City, start: Point, target: Point, min_steps: i64, max_steps: i64) -> i64 {
-    // track positions we've already seen
-    let mut seen = HashSet::new();
-
-    // our search queue, initialised with directions reachable from start
-    let mut queue: BinaryHeap<State> = [North, East, South, West].into_iter()
-        .filter_map(|direction| {
-            if city.bounds().contains(&start.travel(direction)) {
-                Some(State::new(0, Location::new(start, direction), 0))
-            } else { None }
-        })
+///
+/// Returns the lowest heat loss, and the route which has it
+fn search(city: &City, start: Point, target: Point, min_steps: i64, max_steps: i64) -> Option<(i64, Path)> {
+    // start facing each direction which can be travelled in from the start
+    let starts = [North, East, South, West].into_iter()
+        .filter(|direction| city.bounds().contains(&start.travel(*direction)))
+        .map(|direction| (Location::new(start, direction), 0));
+
+    let found = dijkstra(
+        starts,
+        |(location, steps): &Key| moves(city, location, *steps, min_steps, max_steps),
+        |_, (next, _): &Key| *city.get(&next.point).unwrap(),
+        |(location, steps): &Key| location.point == target && *steps >= min_steps,
+    );
+
+    // follow the positions each was reached from back to the start, to find the route taken
+    let goal = found.goal.as_ref()?;
+    let route = found.path_to(goal)?.into_iter()
+        .map(|(location, _)| location.point)
         .collect();
+    Some((found.distance(goal)?, Path::new(route)))
+}
 
-    // search until queue exhausted
-    while let Some(State { cost, location, steps }) = queue.pop() {
-        // have we found the target?
-        if location.point == target && steps >= min_steps {
-            return cost;
-        }
-
-        // have we already seen this point with this number of steps?
-        if !seen.insert((location.clone(), steps)) {
-            continue;
-        }
-
-        // queue up the next search points
-        // forward if max steps not exceeded
-        if steps < max_steps {
-            if let Some(next) = city.travel(&location.point, location.direction) {
-                let cost = cost + city.get(&next).unwrap();
-                queue.push(State::new(cost, Location::new(next, location.direction), steps + 1));
-            }
+/// The positions which can be moved to next: forward if max steps not exceeded, and right and
+/// left if min steps reached
+fn moves(city: &City, location: &Location, steps: i64, min_steps: i64, max_steps: i64) -> Vec<Key> {
+    let mut result = Vec::new();
+    if steps < max_steps {
+        if let Some(next) = city.travel(&location.point, location.direction) {
+            result.push((Location::new(next, location.direction), steps + 1));
         }
-        // right and left if min steps reached
-        if steps >= min_steps {
-            for direction in [
-                location.direction.rotate_clockwise(),
-                location.direction.rotate_anticlockwise()
-            ] {
-                if let Some(next) = city.travel(&location.point, direction) {
-                    let cost = cost + city.get(&next).unwrap();
-                    queue.push(State::new(cost, Location::new(next, direction), 1));
-                }
+    }
+    if steps >= min_steps {
+        for direction in [
+            location.direction.rotate_clockwise(),
+            location.direction.rotate_anticlockwise()
+        ] {
+            if let Some(next) = city.travel(&location.point, direction) {
+                result.push((Location::new(next, direction), 1));
             }
         }
     }
-    panic!("No solution found!")
+    result
 }
 
-
 // -------------------------------------------------------------------------------------------------
 // model
 
-#[derive(Debug, Clone, Hash, Eq, PartialEq, PartialOrd, Ord)]
+#[derive(Debug, Clone, Hash, Eq, PartialEq)]
 struct Location {
     point: Point,
     direction: Direction,
@@ -100,41 +113,74 @@ impl Location {
     }
 }
 
-#[derive(Debug, Clone, Eq, PartialEq, Ord)]
-struct State {
-    /// accumulated heat cost
-    cost: i64,
-    /// current location
-    location: Location,
-    /// number of steps taken in current direction
-    steps: i64,
-}
+/// Identifies a position in the search: where the crucible is, and how long it's been going
+/// in a straight line
+type Key = (Location, i64);
 
-impl State {
-    fn new(cost: i64, position: Location, steps: i64) -> State {
-        State { cost, location: position, steps }
-    }
-}
+type City = Grid<i64>;
 
-impl PartialOrd for State {
-    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
-        Some(other.cost.cmp(&self.cost)) // lowest first ordering
-    }
+// -------------------------------------------------------------------------------------------------
+// visualisation
+
+/// Heat loss shown from cool to hot
+const HEAT: [Colour; 9] = [
+    Colour(40, 60, 160), Colour(50, 100, 190), Colour(50, 150, 200),
+    Colour(60, 180, 160), Colour(130, 200, 90), Colour(210, 200, 60),
+    Colour(240, 150, 40), Colour(230, 90, 30), Colour(200, 30, 30),
+];
+
+/// Show the heat loss of each block as a heatmap, with arrows showing the route taken
+fn _visualise(visualiser: &mut dyn Visualiser, city: &City, route: &Path) {
+    let arrows = route.points().windows(2)
+        .map(|step| {
+            let arrow = match (step[1].x - step[0].x, step[1].y - step[0].y) {
+                (1, _) => ">",
+                (-1, _) => "<",
+                (_, 1) => "v",
+                _ => "^",
+            };
+            (step[1], arrow)
+        })
+        .collect::<HashMap<_, _>>();
+
+    // the route is drawn in bold white, over the heat loss of the blocks it passes through
+    let frame = city.visualise_styled(|cost, p| {
+        let heat = HEAT[(*cost as usize).clamp(1, HEAT.len()) - 1];
+        match arrows.get(p) {
+            Some(arrow) => (arrow.to_string(), Style::new().fg(Colour(255, 255, 255)).bg(heat).bold()),
+            None => (cost.to_string(), Style::new().fg(heat)),
+        }
+    });
+    visualiser.frame(&frame);
+    visualiser.event(&format!("Heat loss: {}", route.points().iter().skip(1).filter_map(|p| city.get(p)).sum::<i64>()));
 }
 
-type City = Grid<i64>;
-
 // -------------------------------------------------------------------------------------------------
 // parsing
 
-fn parse_city(input: &str) -> City {
+fn parse_city(input: &str) -> Result<City, SolveError> {
     // Each city block is marked by a single digit that represents the amount
     // of heat loss if the crucible enters that block
-    let blocks = input.lines()
-        .map(|line| {
-            line.chars().map(|b| b.to_digit(10).unwrap() as i64).collect()
-        })
-        .collect();
+    Ok(City::parse(input, |b, _| b.to_digit(10)
+        .map(|d| d as i64)
+        .ok_or_else(|| SolveError::new(format!("Invalid block: '{}'", b))))?)
+}
+
+// -------------------------------------------------------------------------------------------------
+// tests
 
-    City::new(blocks)
-}
\ No newline at end of file
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn search_route() {
+        let city = parse_city("2413\n3215\n3255").unwrap();
+        let (heat_loss, route) = search(&city, Point::new(0, 0), Point::new(3, 2), 0, 3).unwrap();
+
+        assert_eq!(Point::new(0, 0), route.points()[0]);
+        assert_eq!(Point::new(3, 2), *route.points().last().unwrap());
+        assert_eq!(5, route.len());
+        assert_eq!(heat_loss, route.points().iter().skip(1).map(|p| city.get(p).unwrap()).sum::<i64>());
+    }
+}
@@ -1,18 +1,25 @@
-use std::collections::{HashSet, VecDeque};
+use crate::solutions::{Answer, Harness, Solution, SolveError};
+use crate::solutions::error::{parse_lines, parse_number};
 
-use crate::solutions::Harness;
-
-use crate::utils::point::{Point, Vector, Bounds};
+use crate::utils::grid::Cells;
+use crate::utils::point::{Point, Vector};
 
 use crate::utils::point::Direction::{self, *};
 use crate::utils::path::Path;
+use crate::utils::search::flood_fill;
+use crate::utils::sparse::SparseGrid;
+use crate::visualise::{Colour, Drawing, Palette, Visualiser};
 
 pub struct Day18 {}
 
+pub const SOLUTION: Solution = Solution {
+    day: 18, title: "Lavaduct Lagoon", parts: &[1, 2], new: || Box::new(Day18 {}),
+};
+
 impl Harness for Day18 {
-    fn part_1(&self, input: &str, _visualise: bool) -> i64 {
+    fn part_1(&self, input: &str, visualiser: &mut dyn Visualiser) -> Result<Answer, SolveError> {
         // parse the dig plan
-        let instructions = parse_instructions(input);
+        let instructions = parse_instructions(input)?;
         // trace the path of the digger
         let path = {
             let mut points = Vec::new();
@@ -22,23 +29,37 @@ impl Harness for Day18 {
             }
             Path::new(points)
         };
+        // the trench dug along the path
+        let trench = path.points().iter()
+            .map(|p| (*p, '#'))
+            .collect::<SparseGrid<_>>();
+        if visualiser.enabled() {
+            _visualise(visualiser, &trench);
+            _draw(visualiser, &path);
+        }
 
         // calc the volume of the lagoon
         let volume = {
-            // create a border around the path's extremities
-            let bounds = path.bounds().expand(1);
+            // create a border around the trench's extremities
+            let bounds = trench.bounds().expand(1);
+
+            // flood fill the ground outside the trench. The border joins it all up, so it can be
+            // filled from any corner
+            let outside = flood_fill([bounds.min], |p| {
+                let mut adjacent = trench.adjacent(p);
+                adjacent.retain(|a| !trench.contains(a) && bounds.contains(a));
+                adjacent
+            });
 
-            // flood fill anything outside the lagoon path
-            let filled = flood_fill(bounds.min, &bounds, &path);
             // lagoon_volume = total_volume - exterior_volume
-            bounds.area() - filled.len() as i64
+            bounds.area() - outside.len() as i64
         };
 
-        volume
+        Ok(volume.into())
     }
 
-    fn part_2(&self, input: &str, _visualise: bool) -> i64 {
-        let instructions = parse_instructions_from_colours(input);
+    fn part_2(&self, input: &str, visualiser: &mut dyn Visualiser) -> Result<Answer, SolveError> {
+        let instructions = parse_instructions_from_colours(input)?;
 
         // trace the dig path, including only corners
         let path = Path::new(instructions.iter()
@@ -48,12 +69,17 @@ impl Harness for Day18 {
                 acc.push(next);
                 acc
             }));
+        if visualiser.enabled() { _draw(visualiser, &path); }
 
         // use shoelace formula to calculate the interior area of the lagoon
         let s = shoelace(&path.points());
 
         // Pick's theorum to add the lagoon boundary
-        s + (path.len() / 2) + 1
+        Ok((s + (path.len() / 2) + 1).into())
+    }
+
+    fn parse(&self, input: &str) -> Option<Result<(), SolveError>> {
+        Some(parse_instructions(input).map(|_| ()))
     }
 }
 
@@ -61,47 +87,15 @@ impl Harness for Day18 {
 
 /// Dig a single instruction
 fn dig(start: Point, path: &mut Vec<Point>, instruction: &Instruction) -> Point {
-    let finish = start.apply(Vector::of_distance(instruction.direction, instruction.distance));
+    let step = Vector::of(instruction.direction);
 
-    match instruction.direction {
-        North => for y in (finish.y..start.y).rev() {
-            path.push(Point::new(start.x, y));
-        }
-        South => for y in start.y + 1..=finish.y {
-            path.push(Point::new(start.x, y));
-        }
-        West => for x in (finish.x..start.x).rev() {
-            path.push(Point::new(x, start.y));
-        }
-        East => for x in start.x + 1..=finish.x {
-            path.push(Point::new(x, start.y));
-        }
+    let mut pos = start;
+    for _ in 0..instruction.distance {
+        pos = pos.apply(step);
+        path.push(pos);
     }
 
-    finish
-}
-
-/// BFS flood fill from a starting point, to the intersections with the given path
-fn flood_fill(start: Point, bounds: &Bounds, path: &Path) -> HashSet<Point> {
-    let path = path.points().iter().collect::<HashSet<_>>();
-
-    let mut result = HashSet::new();
-    result.insert(start);
-
-    let mut queue = VecDeque::new();
-    queue.push_back(start);
-
-    let mut visited = HashSet::new();
-    while let Some(p) = queue.pop_front() {
-        if visited.contains(&p) { continue; }
-        visited.insert(p);
-
-        let mut adjacent = p.adjacent();
-        adjacent.retain(|a| !path.contains(a) && bounds.contains(a));
-        result.extend(adjacent.clone());
-        queue.extend(adjacent);
-    }
-    result
+    pos
 }
 
 /// Shoelace formula to calculate area of a polygon
@@ -131,62 +125,72 @@ struct Instruction {
 // -------------------------------------------------------------------------------------------------
 // parsing
 
-fn parse_instructions(input: &str) -> Vec<Instruction> {
-    input.lines()
-        .map(|line| {
-            let parts = line.split_whitespace().collect::<Vec<_>>();
+fn parse_instructions(input: &str) -> Result<Vec<Instruction>, SolveError> {
+    parse_lines(input, |line| {
+        let parts = split_line(line)?;
+
+        let direction = match parts[0] {
+            "U" => North,
+            "D" => South,
+            "L" => West,
+            "R" => East,
+            d => return Err(SolveError::new(format!("Unknown direction: '{}'", d)))
+        };
+
+        let distance = parse_number(parts[1])?;
+
+        Ok(Instruction { direction, distance })
+    })
+}
 
-            let direction = match parts[0] {
-                "U" => North,
-                "D" => South,
-                "L" => West,
-                "R" => East,
-                _ => panic!()
-            };
+fn parse_instructions_from_colours(input: &str) -> Result<Vec<Instruction>, SolveError> {
+    parse_lines(input, |line| {
+        let parts = split_line(line)?;
+        let colour = parts[2].strip_prefix("(#")
+            .and_then(|c| c.strip_suffix(")"))
+            .filter(|c| c.len() == 6 && c.is_ascii())
+            .ok_or_else(|| SolveError::new(format!("Invalid colour: '{}'", parts[2])))?;
+
+        let direction = match &colour[5..] {
+            "0" => East,
+            "1" => South,
+            "2" => West,
+            "3" => North,
+            d => return Err(SolveError::new(format!("Unknown direction: '{}'", d)))
+        };
 
-            let distance = parts[1].parse().unwrap();
+        let distance = i64::from_str_radix(&colour[..5], 16)
+            .map_err(|_| SolveError::new(format!("Invalid distance: '{}'", &colour[..5])))?;
 
-            Instruction { direction, distance }
-        })
-        .collect()
+        Ok(Instruction { direction, distance })
+    })
 }
 
-fn parse_instructions_from_colours(input: &str) -> Vec<Instruction> {
-    input.lines()
-        .map(|line| {
-            let parts = line.split_whitespace().collect::<Vec<_>>();
-            let colour = &parts[2][2..=parts[2].len() - 2];
-
-            let direction = match colour.chars().last().unwrap() {
-                '0' => East,
-                '1' => South,
-                '2' => West,
-                '3' => North,
-                _ => panic!()
-            };
-
-            let distance = i64::from_str_radix(&colour[..=colour.len() - 2], 16).unwrap();
-
-            Instruction { direction, distance }
-        })
-        .collect()
+fn split_line(line: &str) -> Result<Vec<&str>, SolveError> {
+    let parts = line.split_whitespace().collect::<Vec<_>>();
+    if parts.len() != 3 {
+        return Err(SolveError::new(format!("Expected direction, distance and colour: '{}'", line)));
+    }
+    Ok(parts)
 }
 
 // -------------------------------------------------------------------------------------------------
 // visualisation
 
-fn _visualise(bounds: &(Point, Point), path: &[Point]) {
-    let path = path.iter().collect::<HashSet<_>>();
+const LAGOON: Colour = Colour(230, 120, 40);
+const PALETTE: &Palette = &[('#', LAGOON)];
 
-    for y in bounds.0.y..=bounds.1.y {
-        for x in bounds.0.x..=bounds.1.x {
-            if path.contains(&Point::new(x, y)) {
-                print!("#");
-            } else {
-                print!(".");
-            }
-        }
-        println!();
-    }
-    println!();
+fn _visualise(visualiser: &mut dyn Visualiser, trench: &SparseGrid<char>) {
+    let frame = trench.visualise(|v, _| if v.is_some() { "#" } else { "." });
+    visualiser.frame(&frame.with_palette(PALETTE));
+}
+
+/// Draw the outline of the lagoon, which works however large the dig plan is, with a dot for
+/// each point dug to
+fn _draw(visualiser: &mut dyn Visualiser, path: &Path) {
+    let drawing = Drawing::new()
+        .bounds(&path.bounds(), Colour(110, 110, 110))
+        .path(path, LAGOON)
+        .points(path.points(), Colour(255, 210, 60));
+    visualiser.drawing(&drawing);
 }
\ No newline at end of file
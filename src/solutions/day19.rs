@@ -3,13 +3,19 @@ use std::collections::HashMap;
 use Decision::*;
 use Rule::*;
 
-use crate::solutions::Harness;
+use crate::solutions::{Answer, Harness, Solution, SolveError};
+use crate::solutions::error::{parse_number, split_once};
+use crate::visualise::{Graph, Shape, Visualiser};
 
 pub struct Day19 {}
 
+pub const SOLUTION: Solution = Solution {
+    day: 19, title: "Aplenty", parts: &[1, 2], new: || Box::new(Day19 {}),
+};
+
 impl Harness for Day19 {
-    fn part_1(&self, input: &str, _visualise: bool) -> i64 {
-        let (workflows, parts) = parse_input(input);
+    fn part_1(&self, input: &str, _visualiser: &mut dyn Visualiser) -> Result<Answer, SolveError> {
+        let (workflows, parts) = parse_input(input)?;
 
         // identify the accepted parts
         let accepted = parts
@@ -18,14 +24,23 @@ impl Harness for Day19 {
             .collect::<Vec<_>>();
 
         // calculate the result
-        accepted.iter().map(|p| p.tags.values().sum::<i64>()).sum()
+        Ok(accepted.iter().map(|p| p.tags.values().sum::<i64>()).sum::<i64>().into())
     }
 
-    fn part_2(&self, input: &str, _visualise: bool) -> i64 {
-        let (workflows, _) = parse_input(input);
+    fn part_2(&self, input: &str, _visualiser: &mut dyn Visualiser) -> Result<Answer, SolveError> {
+        let (workflows, _) = parse_input(input)?;
 
+        // parsing guarantees the 'in' workflow exists
         let start = workflows.get("in").unwrap();
-        count(&start.rules, &Bounds::new(), &workflows)
+        Ok(count(&start.rules, &Bounds::new(), &workflows).into())
+    }
+
+    fn parse(&self, input: &str) -> Option<Result<(), SolveError>> {
+        Some(parse_input(input).map(|_| ()))
+    }
+
+    fn graph(&self, input: &str) -> Option<Result<Graph, SolveError>> {
+        Some(parse_input(input).map(|(workflows, _)| to_graph(&workflows)))
     }
 }
 
@@ -96,18 +111,35 @@ impl Rule {
     }
 }
 
-impl From<&str> for Rule {
-    fn from(value: &str) -> Self {
-        if value.contains("<") {
-            let (tag, rest) = value.split_once("<").unwrap();
-            let (cmp, res) = rest.split_once(":").unwrap();
-            LessThan(to_tag(tag), cmp.parse().unwrap(), Decision::from(res))
-        } else if value.contains(">") {
-            let (tag, rest) = value.split_once(">").unwrap();
-            let (cmp, res) = rest.split_once(":").unwrap();
-            GreaterThan(to_tag(tag), cmp.parse().unwrap(), Decision::from(res))
+impl TryFrom<&str> for Rule {
+    type Error = SolveError;
+
+    fn try_from(value: &str) -> Result<Self, Self::Error> {
+        if let Some((tag, rest)) = value.split_once("<") {
+            let (cmp, res) = split_once(rest, ":")?;
+            Ok(LessThan(to_tag(tag)?, parse_number(cmp)?, Decision::from(res)))
+        } else if let Some((tag, rest)) = value.split_once(">") {
+            let (cmp, res) = split_once(rest, ":")?;
+            Ok(GreaterThan(to_tag(tag)?, parse_number(cmp)?, Decision::from(res)))
         } else {
-            Decide(Decision::from(value))
+            Ok(Decide(Decision::from(value)))
+        }
+    }
+}
+
+impl Rule {
+    fn decision(&self) -> &Decision {
+        match self {
+            LessThan(_, _, d) | GreaterThan(_, _, d) | Decide(d) => d,
+        }
+    }
+
+    /// The condition for the rule to apply, if it doesn't always
+    fn condition(&self) -> Option<String> {
+        match self {
+            LessThan(t, c, _) => Some(format!("{}<{}", t, c)),
+            GreaterThan(t, c, _) => Some(format!("{}>{}", t, c)),
+            Decide(_) => None,
         }
     }
 }
@@ -201,60 +233,113 @@ fn combinations(bounds: &Bounds) -> i64 {
     product
 }
 
+// -------------------------------------------------------------------------------------------------
+// visualisation
+
+/// The workflows, with an edge for each rule labelled with its condition
+fn to_graph(workflows: &HashMap<String, Workflow>) -> Graph {
+    let mut names = workflows.keys().collect::<Vec<_>>();
+    names.sort();
+
+    let mut graph = Graph::new();
+    for name in &names {
+        graph.node(name, if *name == "in" { Shape::Hexagon } else { Shape::Box });
+    }
+    graph.node("A", Shape::DoubleCircle);
+    graph.node("R", Shape::Circle);
+    graph.highlight("in");
+
+    for name in &names {
+        for rule in &workflows[*name].rules {
+            let to = match rule.decision() {
+                Accept => "A",
+                Reject => "R",
+                Execute(to) => to,
+            };
+            match rule.condition() {
+                Some(condition) => graph.labelled_edge(name, to, &condition),
+                None => graph.edge(name, to),
+            }
+        }
+    }
+
+    graph
+}
+
 // -------------------------------------------------------------------------------------------------
 // parsing
 
-fn parse_input(input: &str) -> (HashMap<String, Workflow>, Vec<Part>) {
+fn parse_input(input: &str) -> Result<(HashMap<String, Workflow>, Vec<Part>), SolveError> {
     let mut workflows = HashMap::new();
+    let mut lines = HashMap::new();
     let mut parts = Vec::new();
 
-    for line in input.lines() {
+    for (i, line) in input.lines().enumerate() {
         if line.is_empty() {
             continue;
         }
         if line.starts_with("{") {
-            parts.push(parse_part(line));
+            parts.push(parse_part(line).map_err(|e| e.on_line(i + 1))?);
         } else {
-            let workflow = parse_workflow(line);
+            let workflow = parse_workflow(line).map_err(|e| e.on_line(i + 1))?;
+            lines.insert(workflow.name.clone(), i + 1);
             workflows.insert(workflow.name.clone(), workflow);
         }
     }
 
-    (workflows, parts)
+    // make sure all the workflows we might need to execute actually exist
+    if !workflows.contains_key("in") {
+        return Err(SolveError::new("No 'in' workflow found"));
+    }
+    for workflow in workflows.values() {
+        for rule in &workflow.rules {
+            if let Execute(name) = rule.decision() {
+                if !workflows.contains_key(name) {
+                    return Err(SolveError::new(format!("Unknown workflow: '{}'", name))
+                        .on_line(lines[&workflow.name]));
+                }
+            }
+        }
+    }
+
+    Ok((workflows, parts))
 }
 
-fn parse_part(line: &str) -> Part {
+fn parse_part(line: &str) -> Result<Part, SolveError> {
     let tags = line
+        .trim_start_matches("{")
+        .trim_end_matches("}")
         .split(",")
-        .map(|s| if s.starts_with("{") { &s[1..] } else { s })
-        .map(|s| {
-            if s.ends_with("}") {
-                &s[..s.len() - 1]
-            } else {
-                s
-            }
+        .map(|p| {
+            let (t, v) = split_once(p, "=")?;
+            Ok((to_tag(t)?, parse_number(v)?))
         })
-        .map(|p| p.split_once("=").unwrap())
-        .map(|(t, v)| (to_tag(t), v.parse().unwrap()))
-        .collect();
+        .collect::<Result<HashMap<_, _>, SolveError>>()?;
 
-    Part { tags }
+    if let Some(t) = ['x', 'm', 'a', 's'].iter().find(|t| !tags.contains_key(t)) {
+        return Err(SolveError::new(format!("Missing rating: '{}'", t)));
+    }
+
+    Ok(Part { tags })
 }
 
-fn parse_workflow(line: &str) -> Workflow {
-    let (name, rules) = line.split_once("{").unwrap();
+fn parse_workflow(line: &str) -> Result<Workflow, SolveError> {
+    let (name, rules) = split_once(line, "{")?;
 
-    let rules = rules[..rules.len() - 1]
+    let rules = rules.trim_end_matches("}")
         .split(",")
-        .map(|r| Rule::from(r))
-        .collect();
+        .map(Rule::try_from)
+        .collect::<Result<_, _>>()?;
 
-    Workflow {
+    Ok(Workflow {
         name: name.to_owned(),
         rules,
-    }
+    })
 }
 
-fn to_tag(value: &str) -> Tag {
-    value.chars().nth(0).unwrap()
+fn to_tag(value: &str) -> Result<Tag, SolveError> {
+    match value {
+        "x" | "m" | "a" | "s" => Ok(value.chars().next().unwrap()),
+        _ => Err(SolveError::new(format!("Unknown rating: '{}'", value)))
+    }
 }
@@ -1,24 +1,32 @@
-use crate::solutions::Harness;
+use crate::solutions::{Answer, Harness, Solution, SolveError};
+use crate::solutions::error::{parse_lines, parse_number, split_once};
+use crate::visualise::Visualiser;
 
 pub struct Day2 {}
 
+pub const SOLUTION: Solution = Solution {
+    day: 2, title: "Cube Conundrum", parts: &[1, 2], new: || Box::new(Day2 {}),
+};
+
 impl Harness for Day2 {
-    fn part_1(&self, input: &str, _visualise: bool) -> i64 {
+    fn part_1(&self, input: &str, _visualiser: &mut dyn Visualiser) -> Result<Answer, SolveError> {
         const RULES: Rgb = (12, 13, 14);
 
-        input.lines()
-            .map(|line| parse_game(line))
+        Ok(parse_lines(input, parse_game)?.iter()
             .filter(|g| is_game_possible(g, &RULES))
             .map(|g| g.id as i64)
-            .sum()
+            .sum::<i64>().into())
     }
 
-    fn part_2(&self, input: &str, _visualise: bool) -> i64 {
-        input.lines()
-            .map(|line| parse_game(line))
+    fn part_2(&self, input: &str, _visualiser: &mut dyn Visualiser) -> Result<Answer, SolveError> {
+        Ok(parse_lines(input, parse_game)?.iter()
             .map(|g| find_min_cubes(&g.rounds))
             .map(|min| (min.0 * min.1 * min.2) as i64)
-            .sum()
+            .sum::<i64>().into())
+    }
+
+    fn parse(&self, input: &str) -> Option<Result<(), SolveError>> {
+        Some(parse_lines(input, parse_game).map(|_| ()))
     }
 }
 
@@ -64,35 +72,37 @@ struct Game {
 // -------------------------------------------------------------------------------------------------
 // parsing
 
-fn parse_game(game: &str) -> Game {
-    let (id, rounds) = game.split_once(":").unwrap();
-    let id = id[5..].parse::<u32>().unwrap();
+fn parse_game(game: &str) -> Result<Game, SolveError> {
+    let (id, rounds) = split_once(game, ":")?;
+    let id = parse_number(id.trim_start_matches("Game"))?;
 
     let rounds: Vec<Rgb> = rounds.split(";")
         .map(|p| parse_round(p))
-        .collect();
+        .collect::<Result<_, _>>()?;
 
-    Game { id, rounds }
+    Ok(Game { id, rounds })
 }
 
-fn parse_round(round: &str) -> Rgb {
+fn parse_round(round: &str) -> Result<Rgb, SolveError> {
     let mut red = 0;
     let mut green = 0;
     let mut blue = 0;
     for cubes in round.split(",") {
         if cubes.ends_with("red") {
-            red = parse_cube(cubes, "red");
+            red = parse_cube(cubes, "red")?;
         } else if cubes.ends_with("green") {
-            green = parse_cube(cubes, "green");
+            green = parse_cube(cubes, "green")?;
         } else if cubes.ends_with("blue") {
-            blue = parse_cube(cubes, "blue");
+            blue = parse_cube(cubes, "blue")?;
+        } else {
+            return Err(SolveError::new(format!("Unknown colour: '{}'", cubes.trim())));
         }
     }
 
-    (red, green, blue)
+    Ok((red, green, blue))
 }
 
-fn parse_cube(cubes: &str, colour: &'static str) -> u32 {
+fn parse_cube(cubes: &str, colour: &'static str) -> Result<u32, SolveError> {
     let len: usize = cubes.len() - colour.len();
-    cubes[..len].trim().parse().unwrap()
+    parse_number(&cubes[..len])
 }
\ No newline at end of file
@@ -2,51 +2,47 @@ use std::collections::{HashMap, HashSet, VecDeque};
 
 use Module::*;
 
-use crate::solutions::Harness;
+use crate::solutions::{Answer, Harness, Solution, SolveError};
+use crate::solutions::error::split_once;
+use crate::visualise::{Graph, Shape, Visualiser};
 
 pub struct Day20 {}
 
+pub const SOLUTION: Solution = Solution {
+    day: 20, title: "Pulse Propagation", parts: &[1, 2], new: || Box::new(Day20 {}),
+};
+
 impl Harness for Day20 {
-    fn part_1(&self, input: &str, visualise: bool) -> i64 {
-        let mut components = parse_configuration(input, visualise);
+    fn part_1(&self, input: &str, visualiser: &mut dyn Visualiser) -> Result<Answer, SolveError> {
+        let mut components = parse_configuration(input)?;
+        if visualiser.enabled() { _visualise(visualiser, &components); }
 
         let monitor = HashSet::new();
 
         let mut total_low = 0;
         let mut total_high = 0;
         for _ in 0..1000 {
-            let (low, high, _) = push_button(&mut components, &monitor, visualise);
+            let (low, high, _) = push_button(&mut components, &monitor, visualiser);
             total_low += low;
             total_high += high;
-            if visualise { println!(); }
+            if visualiser.enabled() { visualiser.event(""); }
         }
 
-        total_low * total_high
+        Ok((total_low * total_high).into())
     }
 
-    fn part_2(&self, input: &str, visualise: bool) -> i64 {
-        let mut components = parse_configuration(input, visualise);
+    fn part_2(&self, input: &str, visualiser: &mut dyn Visualiser) -> Result<Answer, SolveError> {
+        let mut components = parse_configuration(input)?;
+        if visualiser.enabled() { _visualise(visualiser, &components); }
 
         // find the key components to monitor
-        let monitor: HashSet<String> = {
-            // rx is our target component
-            let rx = &components.get("rx");
-            if rx.is_none() { return 0; }
-
-            // rx gets a LOW pulse from this conjunction
-            let conjunction = rx.unwrap().senders.get(0)
-                .map(|n| components.get(n));
-            if conjunction.is_none() { return 0; }
-
-            // the conjunction fires LOW when all its inputs are HIGH
-            conjunction.unwrap().unwrap().senders.iter().cloned().collect()
-        };
+        let monitor = counter_outputs(&components)?;
 
         // start pushing the button
         let mut count = 0;
         let mut push_counts = HashMap::new();
         loop {
-            let (_, _, detected_highs) = push_button(&mut components, &monitor, visualise);
+            let (_, _, detected_highs) = push_button(&mut components, &monitor, visualiser);
             count += 1;
 
             // check if any of our monitored components sent HIGH for first time
@@ -60,7 +56,15 @@ impl Harness for Day20 {
         }
 
         // answer is first time all the tracked inputs simultaneously fire HIGH
-        push_counts.values().product()
+        Ok(push_counts.values().product::<i64>().into())
+    }
+
+    fn parse(&self, input: &str) -> Option<Result<(), SolveError>> {
+        Some(parse_configuration(input).map(|_| ()))
+    }
+
+    fn graph(&self, input: &str) -> Option<Result<Graph, SolveError>> {
+        Some(parse_configuration(input).map(|components| to_graph(&components)))
     }
 }
 
@@ -71,7 +75,8 @@ impl Harness for Day20 {
 /// # Arguments
 /// * `components` - system components (will be updated in place)
 /// * `monitor` - detect HIGH messages sent from these components
-fn push_button(components: &mut HashMap<String, Component>, monitor: &HashSet<String>, visualise: bool)
+fn push_button(components: &mut HashMap<String, Component>, monitor: &HashSet<String>,
+               visualiser: &mut dyn Visualiser)
                -> (i64, i64, HashSet<String>)
 {
     let mut messages: VecDeque<Message> = VecDeque::new();
@@ -83,8 +88,8 @@ fn push_button(components: &mut HashMap<String, Component>, monitor: &HashSet<St
 
     while let Some(message) = messages.pop_front() {
         if message.value { high_count += 1; } else { low_count += 1; }
-        if visualise {
-            println!("{} -{}-> {}", &message.from, if message.value { "high" } else { "low" }, &message.to);
+        if visualiser.enabled() {
+            visualiser.event(&format!("{} -{}-> {}", &message.from, if message.value { "high" } else { "low" }, &message.to));
         }
 
         if message.value && monitor.contains(&message.from) {
@@ -100,6 +105,38 @@ fn push_button(components: &mut HashMap<String, Component>, monitor: &HashSet<St
     (low_count, high_count, monitored_sends)
 }
 
+/// Find the outputs of the counters which feed `rx`. It gets a LOW pulse from a conjunction,
+/// which fires LOW when all its inputs are HIGH. Each of those inputs is driven by a separate
+/// counter, which sends HIGH once per cycle.
+fn counter_outputs(components: &HashMap<String, Component>) -> Result<HashSet<String>, SolveError> {
+    let conjunction = components.get("rx")
+        .and_then(|rx| rx.senders.first())
+        .and_then(|sender| components.get(sender))
+        .ok_or_else(|| SolveError::new("No 'rx' module, or nothing feeds it"))?;
+
+    Ok(conjunction.senders.iter().cloned().collect())
+}
+
+/// The components making up the counter which drives each of the given outputs, ie. everything
+/// upstream of it, apart from the broadcaster which starts them all
+fn counters(components: &HashMap<String, Component>, outputs: &HashSet<String>) -> Vec<(String, HashSet<String>)> {
+    let mut outputs = outputs.iter().cloned().collect::<Vec<_>>();
+    outputs.sort();
+
+    outputs.into_iter()
+        .map(|output| {
+            let mut members = HashSet::new();
+            let mut queue = vec![output.clone()];
+            while let Some(name) = queue.pop() {
+                let Some(component) = components.get(&name) else { continue };
+                if matches!(component.module, Broadcaster | Button) || !members.insert(name) { continue; }
+                queue.extend(component.senders.iter().cloned());
+            }
+            (output, members)
+        })
+        .collect()
+}
+
 // -------------------------------------------------------------------------------------------------
 
 // true = HIGH, false = LOW
@@ -171,7 +208,10 @@ struct Component {
 
 impl Component {
     fn new(name: &str, module: Module, receivers: &str) -> Component {
-        let receivers = receivers.split(",").map(|r| r.trim().to_owned()).collect();
+        let receivers = receivers.split(",")
+            .map(|r| r.trim().to_owned())
+            .filter(|r| !r.is_empty())
+            .collect();
         Component { name: name.to_owned(), module, senders: vec![], receivers }
     }
 
@@ -193,15 +233,15 @@ impl Component {
 
 // -------------------------------------------------------------------------------------------------
 
-fn parse_configuration(config: &str, visualise: bool) -> HashMap<String, Component> {
+fn parse_configuration(config: &str) -> Result<HashMap<String, Component>, SolveError> {
     let mut components = HashMap::new();
 
     // button is implied
     components.insert("button".to_owned(), Component::new("button", Button, "broadcaster"));
 
     // read the components
-    for line in config.lines() {
-        let (mut name, receivers) = line.split_once(" -> ").unwrap();
+    for (i, line) in config.lines().enumerate() {
+        let (mut name, receivers) = split_once(line, " -> ").map_err(|e| e.on_line(i + 1))?;
         let module;
 
         if name == "broadcaster" {
@@ -213,7 +253,7 @@ fn parse_configuration(config: &str, visualise: bool) -> HashMap<String, Compone
             name = &name[1..];
             module = Conjunction(HashMap::new());
         } else {
-            panic!("Unknown module: {}", name);
+            return Err(SolveError::new(format!("Unknown module: '{}'", name)).on_line(i + 1));
         }
 
         components.insert(name.to_owned(), Component::new(name, module, receivers));
@@ -234,15 +274,73 @@ fn parse_configuration(config: &str, visualise: bool) -> HashMap<String, Compone
             .add_senders(senders);
     }
 
-    if visualise {
-        for c in &components {
-            println!("{:?}", c);
+    Ok(components)
+}
+
+// -------------------------------------------------------------------------------------------------
+// visualisation
+
+fn _visualise(visualiser: &mut dyn Visualiser, components: &HashMap<String, Component>) {
+    for c in components {
+        visualiser.event(&format!("{:?}", c));
+    }
+    visualiser.event("");
+}
+
+/// The network of components, with each counter feeding `rx` grouped together, and the
+/// components which combine their outputs highlighted
+fn to_graph(components: &HashMap<String, Component>) -> Graph {
+    let mut names = components.keys().collect::<Vec<_>>();
+    names.sort();
+
+    let mut graph = Graph::new();
+    for name in &names {
+        graph.node(name, match components[*name].module {
+            FlipFlop(_) => Shape::Box,
+            Conjunction(_) => Shape::Diamond,
+            Broadcaster => Shape::Hexagon,
+            Button => Shape::Circle,
+            Sink => Shape::DoubleCircle,
+        });
+    }
+    for name in &names {
+        for receiver in &components[*name].receivers {
+            graph.edge(name, receiver);
+        }
+    }
+
+    if let Ok(outputs) = counter_outputs(components) {
+        for (i, (output, members)) in counters(components, &outputs).iter().enumerate() {
+            let label = format!("counter {} ({})", i + 1, output);
+            for member in members {
+                graph.group(member, &label);
+            }
+        }
+        for name in outputs.iter().chain(components["rx"].senders.iter()) {
+            graph.highlight(name);
         }
-        println!();
+        graph.highlight("rx");
     }
 
-    components
+    graph
 }
 
 // -------------------------------------------------------------------------------------------------
-
+// tests
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn find_counters() {
+        let components = parse_configuration("broadcaster -> a, c\n%a -> b, ca\n%b -> ca\n&ca -> ia\n&ia -> ft\n\
+                                              %c -> cb\n&cb -> ib, c\n&ib -> ft\n&ft -> rx").unwrap();
+        let outputs = counter_outputs(&components).unwrap();
+        assert_eq!(HashSet::from(["ia".to_owned(), "ib".to_owned()]), outputs);
+
+        let names = |names: &[&str]| names.iter().map(|n| n.to_string()).collect::<HashSet<_>>();
+        assert_eq!(vec![("ia".to_owned(), names(&["ia", "ca", "a", "b"])), ("ib".to_owned(), names(&["ib", "cb", "c"]))],
+                   counters(&components, &outputs));
+    }
+}
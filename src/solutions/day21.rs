@@ -1,48 +1,104 @@
 use std::collections::HashSet;
 
-use crate::solutions::Harness;
-use crate::utils::grid::Grid;
-use crate::utils::point::Point;
+use crate::solutions::{Answer, Harness, Solution, SolveError};
+use crate::utils::grid::{Cells, Grid, TiledGrid};
+use crate::utils::point::{Connectivity, Point};
+use crate::utils::search::{bfs, grid_neighbours, Search};
+use crate::visualise::{Colour, Palette, Visualiser};
 
 pub struct Day21 {}
 
+pub const SOLUTION: Solution = Solution {
+    day: 21, title: "Step Counter", parts: &[1, 2], new: || Box::new(Day21 {}),
+};
+
 impl Harness for Day21 {
-    fn part_1(&self, input: &str, visualise: bool) -> i64 {
+    fn part_1(&self, input: &str, visualiser: &mut dyn Visualiser) -> Result<Answer, SolveError> {
         // read grid and find start point
-        let garden = parse_grid(input);
-        let start = garden.find_first(|v| *v == 'S').unwrap();
+        let garden = parse_grid(input)?;
+        let start = find_start(&garden)?;
+
+        // find the fewest steps to each plot
+        let search = bfs([start], grid_neighbours(&garden, Connectivity::Four, |_, v| *v != '#'));
+
+        if visualiser.enabled() {
+            for i in 1..=64 {
+                visualiser.event(&i.to_string());
+                let reachable = reachable_after(&search, i).copied().collect();
+                _visualise(visualiser, &garden, &reachable);
+            }
+        }
 
-        // calculate reachable positions after n steps
-        let mut reachable = HashSet::new();
-        reachable.insert(start);
+        Ok((reachable_after(&search, 64).count() as i64).into())
+    }
 
-        for i in 1..=64 {
-            if visualise { println!("{}", i); }
-            reachable = valid_steps_from(&reachable, &garden);
-            if visualise { _visualise(&garden, &reachable); }
+    fn part_2(&self, input: &str, visualiser: &mut dyn Visualiser) -> Result<Answer, SolveError> {
+        let garden = parse_grid(input)?;
+        let start = find_start(&garden)?;
+        if garden.width() != garden.height() {
+            return Err(SolveError::new("Garden must be square to extrapolate across tiles"));
         }
 
-        reachable.len() as i64
+        let tiled = TiledGrid::new(garden);
+        Ok(count_reachable_tiled(&tiled, start, 26501365, visualiser).into())
     }
 
-    fn part_2(&self, _input: &str, _visualise: bool) -> i64 {
-        // not implemented
-        0
+    fn parse(&self, input: &str) -> Option<Result<(), SolveError>> {
+        Some(parse_grid(input).map(|_| ()))
     }
 }
 
 // -------------------------------------------------------------------------------------------------
 
-fn is_rock(garden: &Garden, p: &Point) -> bool {
+fn find_start(garden: &Garden) -> Result<Point, SolveError> {
+    garden.find_first(|v| *v == 'S')
+        .ok_or_else(|| SolveError::new("No start position 'S' found"))
+}
+
+fn is_rock<G: Cells<char>>(garden: &G, p: &Point) -> bool {
     garden.get(p).is_some_and(|v| *v == '#')
 }
 
-fn valid_steps_from(points: &HashSet<Point>, garden: &Garden) -> HashSet<Point> {
-    points.iter()
-        .flat_map(|p| garden.adjacent(p).into_iter()
-            .filter(|a| !is_rock(garden, &a))
-            .collect::<Vec<_>>())
-        .collect()
+/// The plots which can be reached in exactly `steps` steps, given the fewest steps to each plot.
+/// Once a plot has been reached it can be reached again every other step, by stepping away and
+/// back again.
+fn reachable_after(search: &Search<Point>, steps: i64) -> impl Iterator<Item = &Point> {
+    search.distances()
+        .filter(move |(_, d)| *d <= steps && *d % 2 == steps % 2)
+        .map(|(p, _)| p)
+}
+
+/// Count the plots reachable in exactly `steps` steps on the infinitely tiled garden.
+///
+/// This relies on the shape of the puzzle input: the garden is square, the start is in the
+/// centre, and the start's row and column are free of rocks. The reachable area then expands by
+/// one whole garden in each direction every `size` steps, so the count at `offset + n * size`
+/// steps is a quadratic in `n`. Three samples are enough to extrapolate to the target.
+fn count_reachable_tiled(garden: &TiledGrid<char>, start: Point, steps: i64, visualiser: &mut dyn Visualiser) -> i64 {
+    let size = garden.base().width();
+    let offset = steps % size;
+    let n = steps / size;
+
+    // only the first three repetitions are sampled, so the search can stop there. Nothing
+    // further away than that in a straight line can be reached within it.
+    let limit = steps.min(offset + 2 * size);
+    let search = bfs([start], grid_neighbours(garden, Connectivity::Four, |p, v| {
+        *v != '#' && (p.x - start.x).abs() + (p.y - start.y).abs() <= limit
+    }));
+
+    // sample the reachable count at each repetition
+    let mut samples = Vec::new();
+    for i in (offset..=limit).step_by(size as usize) {
+        let reachable = reachable_after(&search, i).count() as i64;
+        if visualiser.enabled() { visualiser.event(&format!("{} steps: {} plots", i, reachable)); }
+        samples.push(reachable);
+    }
+    // the target was within the sampled range, no need to extrapolate
+    if limit == steps { return samples[samples.len() - 1]; }
+
+    // Newton's forward difference form of the quadratic through the three samples
+    let (s0, s1, s2) = (samples[0], samples[1], samples[2]);
+    s0 + n * (s1 - s0) + n * (n - 1) / 2 * (s2 - 2 * s1 + s0)
 }
 
 // -------------------------------------------------------------------------------------------------
@@ -53,24 +109,25 @@ type Garden = Grid<char>;
 // -------------------------------------------------------------------------------------------------
 // parsing
 
-fn parse_grid(input: &str) -> Garden {
-    let grid = input.lines()
-        .map(|line| line.chars().collect())
-        .collect();
-
-    Garden::new(grid)
+fn parse_grid(input: &str) -> Result<Garden, SolveError> {
+    Ok(Garden::parse(input, |c, _| match c {
+        '.' | '#' | 'S' => Ok(c),
+        _ => Err(SolveError::new(format!("Unknown symbol '{}'", c))),
+    })?)
 }
 
 // -------------------------------------------------------------------------------------------------
 // visualisation
 
-fn _visualise(garden: &Garden, reachable: &HashSet<Point>) {
-    garden.visualise(|_, p| if reachable.contains(p) {
+const PALETTE: &Palette = &[('O', Colour(80, 200, 80)), ('#', Colour(110, 110, 110))];
+
+fn _visualise(visualiser: &mut dyn Visualiser, garden: &Garden, reachable: &HashSet<Point>) {
+    let frame = garden.visualise(|_, p| if reachable.contains(p) {
         "O"
     } else if is_rock(garden, p) {
         "#"
     } else {
         "."
     });
-    println!();
+    visualiser.frame(&frame.with_palette(PALETTE));
 }
\ No newline at end of file
@@ -0,0 +1,158 @@
+use std::collections::HashSet;
+
+use crate::solutions::{Answer, Harness, Solution, SolveError};
+use crate::solutions::error::{parse_lines, parse_number, split_once};
+use crate::utils::grid::Grid;
+use crate::utils::point::Point;
+use crate::visualise::Visualiser;
+
+pub struct Day22 {}
+
+pub const SOLUTION: Solution = Solution {
+    day: 22, title: "Sand Slabs", parts: &[1, 2], new: || Box::new(Day22 {}),
+};
+
+impl Harness for Day22 {
+    fn part_1(&self, input: &str, _visualiser: &mut dyn Visualiser) -> Result<Answer, SolveError> {
+        let mut bricks = parse_bricks(input)?;
+        let supports = settle(&mut bricks);
+
+        // a brick is safe to disintegrate if everything resting on it has another support
+        Ok(((0..bricks.len())
+            .filter(|&b| supports.above[b].iter().all(|&a| supports.below[a].len() > 1))
+            .count() as i64).into())
+    }
+
+    fn part_2(&self, input: &str, _visualiser: &mut dyn Visualiser) -> Result<Answer, SolveError> {
+        let mut bricks = parse_bricks(input)?;
+        let supports = settle(&mut bricks);
+
+        Ok((0..bricks.len())
+            .map(|b| count_falling(b, &supports))
+            .sum::<i64>().into())
+    }
+
+    fn parse(&self, input: &str) -> Option<Result<(), SolveError>> {
+        Some(parse_bricks(input).map(|_| ()))
+    }
+}
+
+// ----------------
+
+/// Let all the bricks fall as far as they can, and work out which bricks end up resting on which.
+/// Bricks are re-ordered from lowest to highest as a side effect.
+fn settle(bricks: &mut [Brick]) -> Supports {
+    bricks.sort_by_key(|b| b.z.0);
+
+    // track the highest brick seen so far above every (x, y) position
+    let width = bricks.iter().map(|b| b.x.1).max().unwrap_or(0) + 1;
+    let depth = bricks.iter().map(|b| b.y.1).max().unwrap_or(0) + 1;
+    let mut heights = HeightMap::new(vec![vec![(0, None); width as usize]; depth as usize]);
+
+    let mut supports = Supports {
+        below: vec![HashSet::new(); bricks.len()],
+        above: vec![HashSet::new(); bricks.len()],
+    };
+
+    for (id, brick) in bricks.iter_mut().enumerate() {
+        let footprint = brick.footprint();
+
+        // drop the brick until it lands on the highest point beneath it
+        let rest = footprint.iter()
+            .filter_map(|p| heights.get(p).map(|(h, _)| *h))
+            .max().unwrap_or(0);
+        let fall = brick.z.0 - rest - 1;
+        brick.z = (brick.z.0 - fall, brick.z.1 - fall);
+
+        // anything at the resting height is now holding this brick up
+        for p in &footprint {
+            if let Some((h, Some(below))) = heights.get(p) {
+                if *h == rest {
+                    supports.below[id].insert(*below);
+                    supports.above[*below].insert(id);
+                }
+            }
+            heights.set(p, (brick.z.1, Some(id)));
+        }
+    }
+
+    supports
+}
+
+/// Count how many other bricks would fall if the given brick were disintegrated
+fn count_falling(brick: usize, supports: &Supports) -> i64 {
+    let mut falling = vec![false; supports.below.len()];
+    falling[brick] = true;
+
+    // bricks are ordered by height, so a brick's supports are always checked before it
+    let mut count = 0;
+    for b in brick + 1..supports.below.len() {
+        let below = &supports.below[b];
+        if !below.is_empty() && below.iter().all(|&s| falling[s]) {
+            falling[b] = true;
+            count += 1;
+        }
+    }
+    count
+}
+
+// -------------------------------------------------------------------------------------------------
+// model
+
+/// A brick, as inclusive (min, max) ranges on each axis
+#[derive(Debug, Clone)]
+struct Brick {
+    x: (i64, i64),
+    y: (i64, i64),
+    z: (i64, i64),
+}
+
+impl Brick {
+    /// The (x, y) points covered by this brick, looking down from above
+    fn footprint(&self) -> Vec<Point> {
+        (self.x.0..=self.x.1)
+            .flat_map(|x| (self.y.0..=self.y.1).map(move |y| Point::new(x, y)))
+            .collect()
+    }
+}
+
+/// The height of the top-most brick at each (x, y) position, and which brick that is
+type HeightMap = Grid<(i64, Option<usize>)>;
+
+/// Which bricks are directly below and above each brick (by index)
+struct Supports {
+    below: Vec<HashSet<usize>>,
+    above: Vec<HashSet<usize>>,
+}
+
+// -------------------------------------------------------------------------------------------------
+// parsing
+
+fn parse_bricks(input: &str) -> Result<Vec<Brick>, SolveError> {
+    parse_lines(input, |line| {
+        let (start, end) = split_once(line, "~")?;
+        let start = parse_coords(start)?;
+        let end = parse_coords(end)?;
+
+        let range = |a: i64, b: i64| (a.min(b), a.max(b));
+        let brick = Brick {
+            x: range(start[0], end[0]),
+            y: range(start[1], end[1]),
+            z: range(start[2], end[2]),
+        };
+
+        if brick.x.0 < 0 || brick.y.0 < 0 || brick.z.0 < 1 {
+            return Err(SolveError::new(format!("Brick is below the ground: '{}'", line)));
+        }
+        Ok(brick)
+    })
+}
+
+fn parse_coords(value: &str) -> Result<[i64; 3], SolveError> {
+    let values = value.split(',')
+        .map(parse_number)
+        .collect::<Result<Vec<_>, _>>()?;
+
+    values.try_into()
+        .map_err(|_| SolveError::new(format!("Expected 3 coordinates: '{}'", value)))
+}
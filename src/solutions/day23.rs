@@ -0,0 +1,145 @@
+use std::collections::HashMap;
+
+use Direction::*;
+
+use crate::solutions::{Answer, Harness, Solution, SolveError};
+use crate::utils::grid::Grid;
+use crate::utils::point::{Direction, Point};
+use crate::visualise::Visualiser;
+
+pub struct Day23 {}
+
+pub const SOLUTION: Solution = Solution {
+    day: 23, title: "A Long Walk", parts: &[1, 2], new: || Box::new(Day23 {}),
+};
+
+impl Harness for Day23 {
+    fn part_1(&self, input: &str, _visualiser: &mut dyn Visualiser) -> Result<Answer, SolveError> {
+        let trails = parse_trails(input)?;
+        longest_hike(&trails, true).map(Answer::from)
+    }
+
+    fn part_2(&self, input: &str, _visualiser: &mut dyn Visualiser) -> Result<Answer, SolveError> {
+        let trails = parse_trails(input)?;
+        longest_hike(&trails, false).map(Answer::from)
+    }
+
+    fn parse(&self, input: &str) -> Option<Result<(), SolveError>> {
+        Some(parse_trails(input).map(|_| ()))
+    }
+}
+
+// ----------------
+
+/// Find the longest hike from the top row to the bottom row, never stepping on the same tile twice
+fn longest_hike(trails: &Trails, slippery: bool) -> Result<i64, SolveError> {
+    let start = trails.find_first(|v| *v == PATH)
+        .ok_or_else(|| SolveError::new("No trails found"))?;
+    let finish = trails.find_all(|v| *v == PATH).pop().unwrap();
+
+    // the trails are mostly long corridors, so compress them into a graph of the junctions
+    let junctions = find_junctions(trails, &start, &finish);
+    let index: HashMap<Point, usize> = junctions.iter().enumerate()
+        .map(|(i, p)| (*p, i))
+        .collect();
+
+    let edges: Vec<Vec<(usize, i64)>> = junctions.iter()
+        .map(|j| {
+            moves(trails, j, slippery).into_iter()
+                .filter_map(|d| walk(trails, j, d, &index, slippery))
+                .collect()
+        })
+        .collect();
+
+    let mut visited = vec![false; junctions.len()];
+    longest_path(index[&start], index[&finish], &edges, &mut visited)
+        .ok_or_else(|| SolveError::new("No route to the finish"))
+}
+
+/// Depth-first search for the longest path between two junctions
+fn longest_path(from: usize, to: usize, edges: &[Vec<(usize, i64)>], visited: &mut [bool]) -> Option<i64> {
+    if from == to { return Some(0); }
+
+    visited[from] = true;
+    let mut best = None;
+    for &(next, distance) in &edges[from] {
+        if visited[next] { continue; }
+        if let Some(d) = longest_path(next, to, edges, visited) {
+            best = best.max(Some(d + distance));
+        }
+    }
+    visited[from] = false;
+
+    best
+}
+
+/// Junctions are any points where the trail splits, plus the start and finish
+fn find_junctions(trails: &Trails, start: &Point, finish: &Point) -> Vec<Point> {
+    let mut junctions = vec![*start, *finish];
+    junctions.extend(trails.points()
+        .filter(|p| !is_forest(trails, p))
+        .filter(|p| trails.adjacent(p).iter().filter(|a| !is_forest(trails, a)).count() > 2));
+    junctions
+}
+
+/// Follow a corridor from a junction in the given direction until reaching another junction.
+/// Returns the junction reached and the number of steps taken, if it's not a dead end.
+fn walk(trails: &Trails, from: &Point, direction: Direction, junctions: &HashMap<Point, usize>,
+        slippery: bool) -> Option<(usize, i64)>
+{
+    let mut prev = *from;
+    let mut pos = trails.travel(from, direction)?;
+    let mut steps = 1;
+
+    while !junctions.contains_key(&pos) {
+        let next = moves(trails, &pos, slippery).into_iter()
+            .filter_map(|d| trails.travel(&pos, d))
+            .find(|n| *n != prev)?;
+
+        prev = pos;
+        pos = next;
+        steps += 1;
+    }
+
+    Some((junctions[&pos], steps))
+}
+
+/// The directions that can be taken from the given point. Slopes only go one way, if slippery.
+fn moves(trails: &Trails, p: &Point, slippery: bool) -> Vec<Direction> {
+    let slope = match trails.get(p) {
+        Some('^') => Some(North),
+        Some('>') => Some(East),
+        Some('v') => Some(South),
+        Some('<') => Some(West),
+        _ => None,
+    };
+
+    match slope {
+        Some(d) if slippery => vec![d],
+        _ => [North, East, South, West].into_iter()
+            .filter(|&d| trails.travel(p, d).is_some_and(|n| !is_forest(trails, &n)))
+            .collect()
+    }
+}
+
+// -------------------------------------------------------------------------------------------------
+// model
+
+type Trails = Grid<char>;
+
+const PATH: char = '.';
+const FOREST: char = '#';
+
+fn is_forest(trails: &Trails, p: &Point) -> bool {
+    trails.get(p).is_some_and(|v| *v == FOREST)
+}
+
+// -------------------------------------------------------------------------------------------------
+// parsing
+
+fn parse_trails(input: &str) -> Result<Trails, SolveError> {
+    Ok(Grid::parse(input, |c, _| match c {
+        '#' | '.' | '^' | '>' | 'v' | '<' => Ok(c),
+        _ => Err(SolveError::new(format!("Unknown symbol '{}'", c))),
+    })?)
+}
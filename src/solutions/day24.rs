@@ -0,0 +1,176 @@
+use crate::solutions::{Answer, Harness, Solution, SolveError};
+use crate::solutions::error::{parse_lines, parse_number, split_once};
+use crate::visualise::Visualiser;
+
+pub struct Day24 {}
+
+pub const SOLUTION: Solution = Solution {
+    day: 24, title: "Never Tell Me The Odds", parts: &[1, 2], new: || Box::new(Day24 {}),
+};
+
+impl Harness for Day24 {
+    fn part_1(&self, input: &str, _visualiser: &mut dyn Visualiser) -> Result<Answer, SolveError> {
+        let hailstones = parse_hailstones(input)?;
+        Ok(count_intersections(&hailstones, 200000000000000.0, 400000000000000.0).into())
+    }
+
+    fn part_2(&self, input: &str, visualiser: &mut dyn Visualiser) -> Result<Answer, SolveError> {
+        let hailstones = parse_hailstones(input)?;
+
+        let rock = find_rock(&hailstones, 1000)
+            .ok_or_else(|| SolveError::new("No rock trajectory hits every hailstone"))?;
+        if visualiser.enabled() { visualiser.event(&format!("Rock: {:?}", rock)); }
+
+        Ok(rock.position.iter().sum::<i64>().into())
+    }
+
+    fn parse(&self, input: &str) -> Option<Result<(), SolveError>> {
+        Some(parse_hailstones(input).map(|_| ()))
+    }
+}
+
+// ----------------
+
+/// Count the pairs of hailstones whose future paths cross (ignoring z) inside the test area
+fn count_intersections(hailstones: &[Hailstone], min: f64, max: f64) -> i64 {
+    let mut count = 0;
+    for (i, a) in hailstones.iter().enumerate() {
+        for b in &hailstones[i + 1..] {
+            if let Some((x, y)) = intersect_xy(a, b) {
+                if x >= min && x <= max && y >= min && y <= max { count += 1; }
+            }
+        }
+    }
+    count
+}
+
+/// Find where two hailstones' paths cross in the x-y plane, if they do so in the future
+fn intersect_xy(a: &Hailstone, b: &Hailstone) -> Option<(f64, f64)> {
+    let [ax, ay, _] = a.position.map(|v| v as f64);
+    let [avx, avy, _] = a.velocity.map(|v| v as f64);
+    let [bx, by, _] = b.position.map(|v| v as f64);
+    let [bvx, bvy, _] = b.velocity.map(|v| v as f64);
+
+    // parallel paths never cross
+    let det = avx * bvy - avy * bvx;
+    if det == 0.0 { return None; }
+
+    // time at which each hailstone reaches the crossing point
+    let t = ((bx - ax) * bvy - (by - ay) * bvx) / det;
+    let s = ((bx - ax) * avy - (by - ay) * avx) / det;
+    if t < 0.0 || s < 0.0 { return None; }
+
+    Some((ax + t * avx, ay + t * avy))
+}
+
+/// Find the rock which, thrown from the right position at the right velocity, will hit every
+/// hailstone.
+///
+/// Viewed from the rock, every hailstone moves with its own velocity minus the rock's, and they
+/// all pass through the rock's starting position. So try each candidate x-y velocity (up to the
+/// given limit) until all the adjusted paths cross at a single point, then solve for z.
+fn find_rock(hailstones: &[Hailstone], limit: i64) -> Option<Hailstone> {
+    if hailstones.len() < 3 { return None; }
+    let (a, b) = (&hailstones[0], &hailstones[1]);
+
+    for vx in -limit..=limit {
+        for vy in -limit..=limit {
+            // relative velocities of the first two hailstones
+            let (avx, avy) = ((a.velocity[0] - vx) as i128, (a.velocity[1] - vy) as i128);
+            let (bvx, bvy) = ((b.velocity[0] - vx) as i128, (b.velocity[1] - vy) as i128);
+
+            let det = avx * bvy - avy * bvx;
+            if det == 0 { continue; }
+
+            // times at which the rock hits each of them, which must be whole numbers
+            let dx = (b.position[0] - a.position[0]) as i128;
+            let dy = (b.position[1] - a.position[1]) as i128;
+            let t_num = dx * bvy - dy * bvx;
+            let s_num = dx * avy - dy * avx;
+            if t_num % det != 0 || s_num % det != 0 { continue; }
+
+            let (t, s) = (t_num / det, s_num / det);
+            if t < 0 || s < 0 || t == s { continue; }
+
+            // the rock's starting position in x-y
+            let x = a.position[0] as i128 + t * avx;
+            let y = a.position[1] as i128 + t * avy;
+
+            // every other hailstone's adjusted path must pass through it too
+            let hits_all = hailstones[2..].iter().all(|h| {
+                let (hvx, hvy) = ((h.velocity[0] - vx) as i128, (h.velocity[1] - vy) as i128);
+                (x - h.position[0] as i128) * hvy == (y - h.position[1] as i128) * hvx
+            });
+            if !hits_all { continue; }
+
+            // knowing when the first two are hit, solve for the z velocity and position
+            let az = a.position[2] as i128 + t * a.velocity[2] as i128;
+            let bz = b.position[2] as i128 + s * b.velocity[2] as i128;
+            if (az - bz) % (t - s) != 0 { continue; }
+            let vz = (az - bz) / (t - s);
+            let z = az - t * vz;
+
+            return Some(Hailstone {
+                position: [x as i64, y as i64, z as i64],
+                velocity: [vx, vy, vz as i64],
+            });
+        }
+    }
+    None
+}
+
+// -------------------------------------------------------------------------------------------------
+// model
+
+#[derive(Debug, Clone)]
+struct Hailstone {
+    position: [i64; 3],
+    velocity: [i64; 3],
+}
+
+// -------------------------------------------------------------------------------------------------
+// parsing
+
+fn parse_hailstones(input: &str) -> Result<Vec<Hailstone>, SolveError> {
+    parse_lines(input, |line| {
+        let (position, velocity) = split_once(line, "@")?;
+        Ok(Hailstone { position: parse_vector(position)?, velocity: parse_vector(velocity)? })
+    })
+}
+
+fn parse_vector(value: &str) -> Result<[i64; 3], SolveError> {
+    let values = value.split(',')
+        .map(parse_number)
+        .collect::<Result<Vec<_>, _>>()?;
+
+    values.try_into()
+        .map_err(|_| SolveError::new(format!("Expected 3 values: '{}'", value.trim())))
+}
+
+// -------------------------------------------------------------------------------------------------
+// tests
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE: &str = "19, 13, 30 @ -2,  1, -2
+18, 19, 22 @ -1, -1, -2
+20, 25, 34 @ -2, -2, -4
+12, 31, 28 @ -1, -2, -1
+20, 19, 15 @  1, -5, -3";
+
+    #[test]
+    fn intersections_in_test_area() {
+        let hailstones = parse_hailstones(EXAMPLE).unwrap();
+        assert_eq!(2, count_intersections(&hailstones, 7.0, 27.0));
+    }
+
+    #[test]
+    fn rock_hits_all_hailstones() {
+        let hailstones = parse_hailstones(EXAMPLE).unwrap();
+        let rock = find_rock(&hailstones, 10).unwrap();
+        assert_eq!([24, 13, 10], rock.position);
+        assert_eq!([-3, 1, 2], rock.velocity);
+    }
+}
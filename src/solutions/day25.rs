@@ -0,0 +1,160 @@
+use std::collections::{HashMap, VecDeque};
+
+use crate::solutions::{Answer, Harness, Solution, SolveError};
+use crate::solutions::error::split_once;
+use crate::utils::search::components;
+use crate::visualise::Visualiser;
+
+pub struct Day25 {}
+
+pub const SOLUTION: Solution = Solution {
+    day: 25, title: "Snowverload", parts: &[1], new: || Box::new(Day25 {}),
+};
+
+impl Harness for Day25 {
+    fn part_1(&self, input: &str, visualiser: &mut dyn Visualiser) -> Result<Answer, SolveError> {
+        let graph = parse_wiring(input)?;
+
+        // cutting wires can only split the components in two if they start off all connected
+        let labels = components(0..graph.edges.len(), |node| graph.edges[*node].clone());
+        let groups = labels.values().max().map_or(0, |last| last + 1);
+        if groups > 1 {
+            return Err(SolveError::new(format!("The components are already in {} separate groups", groups)));
+        }
+
+        // the minimum cut separates the components into exactly two groups
+        let group = split(&graph, 3)
+            .ok_or_else(|| SolveError::new("No way to split the components by cutting 3 wires"))?;
+        if visualiser.enabled() { visualiser.event(&format!("Groups: {} and {}", group, graph.edges.len() - group)); }
+
+        Ok(((group * (graph.edges.len() - group)) as i64).into())
+    }
+
+    fn part_2(&self, _input: &str, _visualiser: &mut dyn Visualiser) -> Result<Answer, SolveError> {
+        // there is no part 2 on the final day, just a big red button to push
+        Ok(Answer::Integer(0))
+    }
+
+    fn parse(&self, input: &str) -> Option<Result<(), SolveError>> {
+        Some(parse_wiring(input).map(|_| ()))
+    }
+}
+
+// ----------------
+
+/// Find the size of one of the two groups left by cutting exactly `cuts` wires.
+///
+/// By max-flow/min-cut, the nodes on the other side of the cut from the first node are exactly
+/// those which can't be reached with more than `cuts` edge-disjoint paths. Once such a node is
+/// found, the source's group is everything still reachable in the residual graph.
+fn split(graph: &Graph, cuts: usize) -> Option<usize> {
+    let source = 0;
+    (1..graph.edges.len())
+        .find_map(|sink| {
+            let mut flow = HashMap::new();
+            for _ in 0..cuts {
+                if !augment(graph, source, sink, &mut flow) { return None; }
+            }
+
+            // can we push any more through? if not, this sink is on the other side of the cut
+            if augment(graph, source, sink, &mut flow) {
+                None
+            } else {
+                Some(reachable(graph, source, &flow).len())
+            }
+        })
+}
+
+/// Push one unit of flow along the shortest path from `source` to `sink` with spare capacity.
+/// Returns false if there is no such path.
+fn augment(graph: &Graph, source: usize, sink: usize, flow: &mut Flow) -> bool {
+    let mut prev = vec![None; graph.edges.len()];
+    prev[source] = Some(source);
+
+    let mut queue = VecDeque::new();
+    queue.push_back(source);
+
+    while let Some(node) = queue.pop_front() {
+        if node == sink { break; }
+        for &next in &graph.edges[node] {
+            if prev[next].is_none() && has_capacity(flow, node, next) {
+                prev[next] = Some(node);
+                queue.push_back(next);
+            }
+        }
+    }
+
+    if prev[sink].is_none() { return false; }
+
+    // walk back along the path, recording the flow
+    let mut node = sink;
+    while node != source {
+        let from = prev[node].unwrap();
+        *flow.entry((from, node)).or_insert(0) += 1;
+        *flow.entry((node, from)).or_insert(0) -= 1;
+        node = from;
+    }
+    true
+}
+
+/// Every wire has a capacity of 1 in each direction
+fn has_capacity(flow: &Flow, from: usize, to: usize) -> bool {
+    flow.get(&(from, to)).copied().unwrap_or(0) < 1
+}
+
+/// All the nodes reachable from `source` via wires that still have spare capacity
+fn reachable(graph: &Graph, source: usize, flow: &Flow) -> Vec<usize> {
+    let mut seen = vec![false; graph.edges.len()];
+    seen[source] = true;
+
+    let mut queue = VecDeque::new();
+    queue.push_back(source);
+
+    let mut result = Vec::new();
+    while let Some(node) = queue.pop_front() {
+        result.push(node);
+        for &next in &graph.edges[node] {
+            if !seen[next] && has_capacity(flow, node, next) {
+                seen[next] = true;
+                queue.push_back(next);
+            }
+        }
+    }
+    result
+}
+
+// -------------------------------------------------------------------------------------------------
+// model
+
+/// Undirected graph of components, as adjacency lists indexed by component number
+struct Graph {
+    edges: Vec<Vec<usize>>,
+}
+
+/// Flow along each directed (from, to) wire
+type Flow = HashMap<(usize, usize), i64>;
+
+// -------------------------------------------------------------------------------------------------
+// parsing
+
+fn parse_wiring(input: &str) -> Result<Graph, SolveError> {
+    let mut ids: HashMap<&str, usize> = HashMap::new();
+    let mut edges: Vec<Vec<usize>> = Vec::new();
+
+    let mut id = |name, edges: &mut Vec<Vec<usize>>| *ids.entry(name).or_insert_with(|| {
+        edges.push(Vec::new());
+        edges.len() - 1
+    });
+
+    for (i, line) in input.lines().enumerate() {
+        let (name, connected) = split_once(line, ":").map_err(|e| e.on_line(i + 1))?;
+        let from = id(name.trim(), &mut edges);
+        for other in connected.split_whitespace() {
+            let to = id(other, &mut edges);
+            edges[from].push(to);
+            edges[to].push(from);
+        }
+    }
+
+    Ok(Graph { edges })
+}
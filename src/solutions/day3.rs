@@ -1,210 +1,153 @@
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
 
-use crate::solutions::Harness;
+use crate::solutions::{Answer, Harness, Solution, SolveError};
+use crate::solutions::error::parse_number;
+use crate::utils::grid::Grid;
+use crate::utils::point::Point;
+use crate::visualise::Visualiser;
 
 pub struct Day3 {}
 
+pub const SOLUTION: Solution = Solution {
+    day: 3, title: "Gear Ratios", parts: &[1, 2], new: || Box::new(Day3 {}),
+};
+
 impl Harness for Day3 {
-    fn part_1(&self, input: &str, _visualise: bool) -> i64 {
-        // keep track of things which might become part numbers
-        let mut pending_numbers = Vec::new();
-        let mut pending_symbols = Vec::new();
-
-        let mut result = 0;
-        // scan line-by-line
-        for (idx, line) in input.lines().enumerate() {
-            // get the numbers and symbols from the current line
-            let (numbers, symbols) = parse_line(idx, line);
-
-            pending_numbers.extend(numbers);
-            pending_symbols.extend(symbols);
-
-            // look for any valid part numbers
-            let part_numbers = find_part_numbers(&mut pending_numbers, &pending_symbols);
-            result += part_numbers.iter().map(|p| p.number.value).sum::<i64>();
-
-            // drop any pending values which can no longer be matched
-            if idx > 1 {
-                pending_numbers.retain(|n| n.line > idx - 1);
-                pending_symbols.retain(|s| s.line > idx - 1);
-            }
-        }
-        result
+    fn part_1(&self, input: &str, _visualiser: &mut dyn Visualiser) -> Result<Answer, SolveError> {
+        let schematic = parse_schematic(input)?;
+
+        // any number next to a symbol is a part number
+        let result = find_numbers(&schematic)?.iter()
+            .filter(|n| !adjacent_symbols(&schematic, n).is_empty())
+            .map(|n| n.value)
+            .sum::<i64>();
+        Ok(result.into())
     }
 
-    fn part_2(&self, input: &str, _visualise: bool) -> i64 {
-        // keep track of things which might become part numbers, or gears
-        let mut pending_numbers = Vec::new();
-        let mut pending_symbols = Vec::new();
-        let mut pending_gears: Vec<PartNumber> = Vec::new();
-
-        let mut result: i64 = 0;
-        // scan line-by-line
-        for (idx, line) in input.lines().enumerate() {
-            // get the numbers and symbols from the current line
-            let (numbers, symbols) = parse_line(idx, line);
-
-            pending_numbers.extend(numbers);
-            pending_symbols.extend(symbols);
-
-            // look for valid part numbers or gears
-            let part_numbers = find_part_numbers(&mut pending_numbers, &pending_symbols);
-            let mut partial_gears = find_gears(&part_numbers);
-            let gears = match_gears(&mut pending_gears, &mut partial_gears);
-            result += gears.iter().map(|g| g.product()).sum::<i64>();
-            pending_gears.extend(partial_gears);
-
-            // drop any pending values which can no longer be matched
-            if idx > 1 {
-                pending_numbers.retain(|n| n.line > idx - 1);
-                pending_symbols.retain(|s| s.line > idx - 1);
-                pending_gears.retain(|g| g.symbol.line > idx - 1);
+    fn part_2(&self, input: &str, _visualiser: &mut dyn Visualiser) -> Result<Answer, SolveError> {
+        let schematic = parse_schematic(input)?;
+
+        // collect the part numbers next to each '*'
+        let mut gears: HashMap<Point, Vec<i64>> = HashMap::new();
+        for number in find_numbers(&schematic)? {
+            for symbol in adjacent_symbols(&schematic, &number) {
+                if schematic.get(&symbol) == Some(&'*') {
+                    gears.entry(symbol).or_default().push(number.value);
+                }
             }
         }
-        result
+
+        // it's only a gear if it's next to exactly two of them
+        let result = gears.values()
+            .filter(|parts| parts.len() == 2)
+            .map(|parts| parts[0] * parts[1])
+            .sum::<i64>();
+        Ok(result.into())
+    }
+
+    fn parse(&self, input: &str) -> Option<Result<(), SolveError>> {
+        Some(parse_schematic(input).map(|_| ()))
     }
 }
 
 // ----------------
 
-fn find_part_numbers(numbers: &mut Vec<Number>, symbols: &Vec<Symbol>) -> Vec<PartNumber> {
-    let mut part_numbers = Vec::new();
-
-    let mut retain = Vec::new();
-    for number in numbers.drain(..) {
-        match find_adjacent_symbol(&number, &symbols) {
-            Some(symbol) => part_numbers.push(PartNumber { number, symbol: symbol.clone() }),
-            None => retain.push(number)
+/// Find all the numbers in the schematic, reading each row from left to right
+fn find_numbers(schematic: &Schematic) -> Result<Vec<Number>, SolveError> {
+    let mut numbers = Vec::new();
+    for y in 0..schematic.height() {
+        let mut digits = String::new();
+        let mut points = Vec::new();
+        // one past the end of the row, so a number at the edge is finished too
+        for x in 0..=schematic.width() {
+            let p = Point::new(x, y);
+            match schematic.get(&p).filter(|c| c.is_ascii_digit()) {
+                Some(c) => {
+                    digits.push(*c);
+                    points.push(p);
+                }
+                None if !digits.is_empty() => {
+                    let value = parse_number(&digits).map_err(|e| e.on_line(y as usize + 1))?;
+                    numbers.push(Number { value, points: std::mem::take(&mut points) });
+                    digits.clear();
+                }
+                None => {}
+            }
         }
     }
-    numbers.extend(retain);
-
-    part_numbers
+    Ok(numbers)
 }
 
-fn find_gears(parts: &Vec<PartNumber>) -> Vec<PartNumber> {
-    parts.iter()
-        .filter(|p| p.symbol.value == '*')
-        .map(|p| p.clone())
+/// Find the symbols next to any digit of the number, including diagonally
+fn adjacent_symbols(schematic: &Schematic, number: &Number) -> HashSet<Point> {
+    number.points.iter()
+        .flat_map(|p| schematic.neighbours8(p))
+        .filter(|n| schematic.get(n).is_some_and(|c| is_symbol(*c)))
         .collect()
 }
 
-fn match_gears(partials: &mut Vec<PartNumber>, parts: &mut Vec<PartNumber>) -> Vec<Gear> {
-    let mut matched = Vec::new();
-
-    // index the partials by symbol instance
-    let mut unmatched: HashMap<_, _> = partials.drain(..)
-        .map(|p| (p.symbol.clone(), p))
-        .collect();
-
-    // check each new part for a match in the partials index
-    for part in parts.drain(..) {
-        match unmatched.remove(&part.symbol) {
-            Some(p) => matched.push(Gear::new(p, part)),
-            None => { unmatched.insert(part.symbol.clone(), part); }
-        };
-    }
-
-    // insert any remaining unmatched gears into the partials list
-    partials.extend(unmatched.values().cloned().collect::<Vec<_>>());
-
-    matched
-}
-
-fn find_adjacent_symbol<'a>(num: &Number, symbols: &'a Vec<Symbol>) -> Option<&'a Symbol> {
-    symbols.iter()
-        .filter(|s| is_adjacent(num, s))
-        .next()
-}
-
-fn is_adjacent(num: &Number, symbol: &Symbol) -> bool {
-    let sub = |lhs: usize, rhs: usize| lhs.checked_sub(rhs).unwrap_or(0);
-
-    let line_match = (sub(num.line, 1)..=num.line + 1).contains(&symbol.line);
-    let idx_match = (sub(num.start, 1)..=num.end + 1).contains(&symbol.idx);
-    line_match && idx_match
+/// Anything other than a digit or a dot is a symbol
+fn is_symbol(c: char) -> bool {
+    !c.is_ascii_digit() && c != '.'
 }
 
 // -------------------------------------------------------------------------------------------------
 // model
 
+type Schematic = Grid<char>;
+
 #[derive(Debug, Clone)]
 struct Number {
     value: i64,
-    line: usize,
-    start: usize,
-    end: usize,
-}
-
-#[derive(Debug, Clone, Hash, PartialEq, Eq)]
-struct Symbol {
-    value: char,
-    line: usize,
-    idx: usize,
-}
-
-#[derive(Debug, Clone)]
-struct PartNumber {
-    number: Number,
-    symbol: Symbol,
-}
-
-#[derive(Debug)]
-struct Gear {
-    part_a: PartNumber,
-    part_b: PartNumber,
-}
-
-impl Gear {
-    fn new<>(a: PartNumber, b: PartNumber) -> Gear {
-        Gear { part_a: a, part_b: b }
-    }
-
-    fn product(&self) -> i64 {
-        self.part_a.number.value * self.part_b.number.value
-    }
+    /// the point of each of its digits
+    points: Vec<Point>,
 }
 
 // -------------------------------------------------------------------------------------------------
 // parsing
 
-fn parse_line(line_num: usize, line: &str) -> (Vec<Number>, Vec<Symbol>) {
-    let mut symbols = Vec::new();
-    let mut numbers = Vec::new();
-
-    let mut cur_num = "".to_owned();
-    for (idx, char) in line.chars().enumerate() {
-        // if digit, construct numeric value
-        if char.is_digit(10) {
-            cur_num.push(char);
-            continue;
-        }
-
-        // not digit, finish any numeric value
-        if !cur_num.is_empty() {
-            numbers.push(to_number(&cur_num, line_num, idx));
-            cur_num.clear();
-        }
-
-        // dot is just padding
-        if char == '.' { continue; }
+fn parse_schematic(input: &str) -> Result<Schematic, SolveError> {
+    Ok(input.parse::<Schematic>()?)
+}
 
-        // symbols are hotspots for part numbers
-        symbols.push(Symbol { value: char, line: line_num, idx });
+// -------------------------------------------------------------------------------------------------
+// tests
+
+#[cfg(test)]
+mod tests {
+    use crate::visualise::NullVisualiser;
+
+    use super::*;
+
+    const EXAMPLE: &str = "467..114..
+...*......
+..35..633.
+......#...
+617*......
+.....+.58.
+..592.....
+......755.
+...$.*....
+.664.598..";
+
+    #[test]
+    fn example() {
+        assert_eq!(Ok(Answer::Integer(4361)), Day3 {}.part_1(EXAMPLE, &mut NullVisualiser));
+        assert_eq!(Ok(Answer::Integer(467835)), Day3 {}.part_2(EXAMPLE, &mut NullVisualiser));
     }
 
-    if !cur_num.is_empty() {
-        numbers.push(to_number(&cur_num, line_num, line.len()));
+    #[test]
+    fn gear_needs_exactly_two_parts() {
+        // the '*' touches three numbers, so it isn't a gear, but they're all still parts
+        let schematic = "2.3\n.*.\n4..";
+        assert_eq!(Ok(Answer::Integer(9)), Day3 {}.part_1(schematic, &mut NullVisualiser));
+        assert_eq!(Ok(Answer::Integer(0)), Day3 {}.part_2(schematic, &mut NullVisualiser));
     }
 
-    (numbers, symbols)
-}
-
-fn to_number(txt: &str, line: usize, idx: usize) -> Number {
-    Number {
-        value: txt.parse().unwrap(),
-        line,
-        start: idx - txt.len(),
-        end: idx - 1,
+    #[test]
+    fn number_too_large() {
+        let schematic = ".....................\n*99999999999999999999";
+        let error = Day3 {}.part_1(schematic, &mut NullVisualiser).unwrap_err();
+        assert_eq!("line 2: Invalid number: '99999999999999999999'", error.to_string());
     }
 }
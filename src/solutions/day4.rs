@@ -1,24 +1,28 @@
 use std::collections::{BTreeSet, HashMap};
 
-use crate::solutions::Harness;
+use crate::solutions::{Answer, Harness, Solution, SolveError};
+use crate::solutions::error::{parse_lines, parse_number, split_once};
+use crate::visualise::Visualiser;
 
 pub struct Day4 {}
 
+pub const SOLUTION: Solution = Solution {
+    day: 4, title: "Scratchcards", parts: &[1, 2], new: || Box::new(Day4 {}),
+};
+
 impl Harness for Day4 {
-    fn part_1(&self, input: &str, _visualise: bool) -> i64 {
-        input.lines()
-            .map(|line| parse_line(line))
-            .map(|card| calculate_matches(&card))
+    fn part_1(&self, input: &str, _visualiser: &mut dyn Visualiser) -> Result<Answer, SolveError> {
+        Ok((parse_lines(input, parse_line)?.iter()
+            .map(calculate_matches)
             .map(|matches| if matches > 0 { 2_u32.pow(matches - 1) } else { 0 })
-            .sum::<u32>() as i64
+            .sum::<u32>() as i64).into())
     }
 
-    fn part_2(&self, input: &str, _visualise: bool) -> i64 {
+    fn part_2(&self, input: &str, _visualiser: &mut dyn Visualiser) -> Result<Answer, SolveError> {
         let mut matches_per_card = HashMap::new();
         let mut num_copies: HashMap<u32, u32> = HashMap::new();
 
-        input.lines()
-            .map(|line| parse_line(line))
+        parse_lines(input, parse_line)?.iter()
             .for_each(|card| {
                 let num_matches = calculate_matches(&card);
                 matches_per_card.insert(card.id, num_matches);
@@ -30,7 +34,11 @@ impl Harness for Day4 {
             update_copies(card_id as u32, &mut num_copies, &matches_per_card);
         }
 
-        num_copies.values().sum::<u32>() as i64
+        Ok((num_copies.values().sum::<u32>() as i64).into())
+    }
+
+    fn parse(&self, input: &str) -> Option<Result<(), SolveError>> {
+        Some(parse_lines(input, parse_line).map(|_| ()))
     }
 }
 
@@ -66,17 +74,16 @@ struct Card {
 // -------------------------------------------------------------------------------------------------
 // parsing
 
-fn parse_line(line: &str) -> Card {
+fn parse_line(line: &str) -> Result<Card, SolveError> {
     // grab the id
-    let id = line.split(":").collect::<Vec<_>>()[0][5..]
-        .trim().parse::<u32>().unwrap();
+    let (id, rest) = split_once(line, ":")?;
+    let id = parse_number(id.trim_start_matches("Card"))?;
 
     // separate numbers from winning
-    let parts: Vec<&str> = line[line.find(":").unwrap_or(0) + 1..]
-        .split("|").collect();
+    let (numbers, winning) = split_once(rest, "|")?;
 
-    let numbers = parts[0].split_whitespace().map(|n| n.parse().unwrap()).collect();
-    let winning = parts[1].split_whitespace().map(|n| n.parse().unwrap()).collect();
+    let numbers = numbers.split_whitespace().map(parse_number).collect::<Result<_, _>>()?;
+    let winning = winning.split_whitespace().map(parse_number).collect::<Result<_, _>>()?;
 
-    Card { id, numbers, winning }
+    Ok(Card { id, numbers, winning })
 }
\ No newline at end of file
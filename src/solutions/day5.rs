@@ -2,42 +2,55 @@ use std::cmp::Ordering;
 use std::collections::VecDeque;
 use std::fmt::{Debug, Formatter};
 
-use crate::solutions::Harness;
+use crate::solutions::{Answer, Harness, Solution, SolveError};
+use crate::solutions::error::parse_number;
+use crate::visualise::Visualiser;
 
 pub struct Day5 {}
 
+pub const SOLUTION: Solution = Solution {
+    day: 5, title: "If You Give A Seed A Fertilizer", parts: &[1, 2], new: || Box::new(Day5 {}),
+};
+
 impl Harness for Day5 {
-    fn part_1(&self, input: &str, _visualise: bool) -> i64 {
-        let (seeds, mappings) = parse_input(input);
+    fn part_1(&self, input: &str, _visualiser: &mut dyn Visualiser) -> Result<Answer, SolveError> {
+        let (seeds, mappings) = parse_input(input)?;
 
         let apply_mapping = |seed| mappings.iter()
             .fold(seed, |v, m| apply_mapping(v, m));
 
         // calculate the location for each input seed, by applying each
         // mapping in turn, and find the smallest value
-        seeds.iter()
+        Ok(seeds.iter()
             .map(|&seed| apply_mapping(seed))
-            .min().unwrap_or(0)
+            .min().unwrap_or(0).into())
     }
 
-    fn part_2(&self, input: &str, visualise: bool) -> i64 {
-        let (seeds, mut mappings) = parse_input(input);
+    fn part_2(&self, input: &str, visualiser: &mut dyn Visualiser) -> Result<Answer, SolveError> {
+        let (seeds, mut mappings) = parse_input(input)?;
 
         // treat the seeds as ranges rather than individual items, and order them
+        if seeds.len() % 2 != 0 {
+            return Err(SolveError::new("Seeds should be (start, length) pairs").on_line(1));
+        }
         let mut seed_ranges = to_ranges(&seeds);
         seed_ranges.sort();
-        if visualise { println!("Seed ranges: {:?}\n", seed_ranges) };
+        if visualiser.enabled() { visualiser.event(&format!("Seed ranges: {:?}\n", seed_ranges)) };
 
         // make sure the mappings are all sorted
         mappings.iter_mut().for_each(|m| m.sort());
 
         // apply the mappings for each range in bulk
-        let locations = apply_mappings_to_ranges(seed_ranges, &mappings, visualise);
+        let locations = apply_mappings_to_ranges(seed_ranges, &mappings, visualiser);
 
         // find the smallest value in the location ranges
-        locations.iter()
+        Ok(locations.iter()
             .map(|r| r.min)
-            .min().unwrap_or(0)
+            .min().unwrap_or(0).into())
+    }
+
+    fn parse(&self, input: &str) -> Option<Result<(), SolveError>> {
+        Some(parse_input(input).map(|_| ()))
     }
 }
 
@@ -65,16 +78,16 @@ fn to_ranges(values: &[i64]) -> Vec<Range> {
 }
 
 
-fn apply_mappings_to_ranges(ranges: Vec<Range>, mappings: &[Mapping], visualise: bool) -> Vec<Range> {
+fn apply_mappings_to_ranges(ranges: Vec<Range>, mappings: &[Mapping], visualiser: &mut dyn Visualiser) -> Vec<Range> {
     let mut result = Vec::from(ranges);
     for mapping in mappings {
         result = result.into_iter()
             .flat_map(|range| apply_mapping_to_range(range, mapping))
             .collect();
 
-        if visualise {
-            println!("Applying mapping:\n\t {:?}", mapping);
-            println!("Result:\n\t {:?}\n", result);
+        if visualiser.enabled() {
+            visualiser.event(&format!("Applying mapping:\n\t {:?}", mapping));
+            visualiser.event(&format!("Result:\n\t {:?}\n", result));
         }
     }
     result
@@ -215,23 +228,25 @@ type Mapping = Vec<Transform>;
 // -------------------------------------------------------------------------------------------------
 // parsing
 
-fn parse_input(input: &str) -> (Vec<i64>, Vec<Mapping>) {
+fn parse_input(input: &str) -> Result<(Vec<i64>, Vec<Mapping>), SolveError> {
     let mut seeds = Vec::new();
     let mut mappings = Vec::new();
 
     let mut mapping_in_progress = Vec::new();
-    for line in input.lines() {
-        if line.starts_with("seeds") {
+    for (i, line) in input.lines().enumerate() {
+        if let Some(values) = line.strip_prefix("seeds:") {
             // list of seeds, all on one line
-            seeds = line[6..].split_whitespace()
-                .map(|s| s.parse().unwrap()).collect();
+            seeds = values.split_whitespace()
+                .map(parse_number)
+                .collect::<Result<_, _>>()
+                .map_err(|e| e.on_line(i + 1))?;
         } else if line.is_empty() && !mapping_in_progress.is_empty() {
             // end of mapping
             mappings.push(mapping_in_progress);
             mapping_in_progress = Vec::new();
         } else if !line.is_empty() && !line.contains("map") {
             // mapping item
-            mapping_in_progress.push(to_mapping(line));
+            mapping_in_progress.push(to_mapping(line).map_err(|e| e.on_line(i + 1))?);
         }
     }
 
@@ -239,16 +254,20 @@ fn parse_input(input: &str) -> (Vec<i64>, Vec<Mapping>) {
         mappings.push(mapping_in_progress);
     }
 
-    (seeds, mappings)
+    Ok((seeds, mappings))
 }
 
-fn to_mapping(line: &str) -> Transform {
-    let values: Vec<i64> = line.split_whitespace().map(|s| s.parse().unwrap()).collect();
+fn to_mapping(line: &str) -> Result<Transform, SolveError> {
+    let values: Vec<i64> = line.split_whitespace().map(parse_number).collect::<Result<_, _>>()?;
+    if values.len() != 3 {
+        return Err(SolveError::new(format!("Expected 3 values in mapping: '{}'", line)));
+    }
+
     let src = values[1];
     let dst = values[0];
     let len = values[2];
 
-    Transform { range: Range::new(src, src + len - 1), offset: dst - src }
+    Ok(Transform { range: Range::new(src, src + len - 1), offset: dst - src })
 }
 
 // -------------------------------------------------------------------------------------------------
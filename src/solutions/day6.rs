@@ -1,21 +1,31 @@
 use std::iter;
 
-use crate::solutions::Harness;
+use crate::solutions::{Answer, Harness, Solution, SolveError};
+use crate::solutions::error::{parse_number, split_once};
+use crate::visualise::Visualiser;
 
 pub struct Day6 {}
 
+pub const SOLUTION: Solution = Solution {
+    day: 6, title: "Wait For It", parts: &[1, 2], new: || Box::new(Day6 {}),
+};
+
 impl Harness for Day6 {
-    fn part_1(&self, input: &str, _visualise: bool) -> i64 {
-        let races = parse_races(input);
+    fn part_1(&self, input: &str, _visualiser: &mut dyn Visualiser) -> Result<Answer, SolveError> {
+        let races = parse_races(input)?;
+
+        Ok(races.iter()
+            .map(count_winners)
+            .product::<i64>().into())
+    }
 
-        races.iter()
-            .map(|r| count_winners(&r))
-            .product()
+    fn part_2(&self, input: &str, _visualiser: &mut dyn Visualiser) -> Result<Answer, SolveError> {
+        let race = parse_single_race(input)?;
+        Ok(count_winners(&race).into())
     }
 
-    fn part_2(&self, input: &str, _visualise: bool) -> i64 {
-        let race = parse_single_race(input);
-        count_winners(&race)
+    fn parse(&self, input: &str) -> Option<Result<(), SolveError>> {
+        Some(parse_races(input).map(|_| ()))
     }
 }
 
@@ -42,38 +52,41 @@ struct Race {
 // -------------------------------------------------------------------------------------------------
 // parsing
 
-fn parse_races(input: &str) -> Vec<Race> {
-    fn parse_numbers(value: &str) -> Vec<i64> {
-        value.split_once(":").unwrap().1
+fn parse_races(input: &str) -> Result<Vec<Race>, SolveError> {
+    fn parse_numbers(value: &str) -> Result<Vec<i64>, SolveError> {
+        split_once(value, ":")?.1
             .split_whitespace()
-            .map(|s| s.parse().unwrap())
+            .map(parse_number)
             .collect()
     }
 
     _parse_file(input, parse_numbers)
 }
 
-fn parse_single_race(input: &str) -> Race {
-    fn parse_number(value: &str) -> Vec<i64> {
-        let num = value.split_once(":").unwrap().1
+fn parse_single_race(input: &str) -> Result<Race, SolveError> {
+    fn parse_single_number(value: &str) -> Result<Vec<i64>, SolveError> {
+        let num = split_once(value, ":")?.1
             .split_whitespace()
-            .fold(String::new(), |a, b| a + b)
-            .parse().unwrap();
-        vec![num]
+            .fold(String::new(), |a, b| a + b);
+        Ok(vec![parse_number(&num)?])
     }
 
-    _parse_file(input, parse_number).remove(0)
+    _parse_file(input, parse_single_number)?.into_iter().next()
+        .ok_or_else(|| SolveError::new("No race found"))
 }
 
-fn _parse_file<F: Fn(&str) -> Vec<i64>>(input: &str, parse_numbers: F) -> Vec<Race> {
+fn _parse_file<F>(input: &str, parse_numbers: F) -> Result<Vec<Race>, SolveError>
+    where F: Fn(&str) -> Result<Vec<i64>, SolveError>
+{
     let mut times: Vec<i64> = Vec::new();
     let mut distances: Vec<i64> = Vec::new();
-    for line in input.lines() {
-        if line.contains("Time:") { times = parse_numbers(line); }
-        if line.contains("Distance:") { distances = parse_numbers(line); }
+    for (i, line) in input.lines().enumerate() {
+        let parse = |line| parse_numbers(line).map_err(|e| e.on_line(i + 1));
+        if line.contains("Time:") { times = parse(line)?; }
+        if line.contains("Distance:") { distances = parse(line)?; }
     }
 
-    iter::zip(times, distances)
+    Ok(iter::zip(times, distances)
         .map(|(time, record)| Race { time, record })
-        .collect()
+        .collect())
 }
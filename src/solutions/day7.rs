@@ -2,35 +2,44 @@ use std::cmp::Ordering;
 use std::collections::HashMap;
 use std::iter::zip;
 
-use crate::solutions::Harness;
+use crate::solutions::{Answer, Harness, Solution, SolveError};
+use crate::solutions::error::{parse_lines, parse_number, split_once};
+use crate::visualise::Visualiser;
 
 pub struct Day7 {}
 
+pub const SOLUTION: Solution = Solution {
+    day: 7, title: "Camel Cards", parts: &[1, 2], new: || Box::new(Day7 {}),
+};
+
 impl Harness for Day7 {
-    fn part_1(&self, input: &str, _visualise: bool) -> i64 {
+    fn part_1(&self, input: &str, _visualiser: &mut dyn Visualiser) -> Result<Answer, SolveError> {
         let jokers_wild = false;
         solve(input, jokers_wild)
     }
 
-    fn part_2(&self, input: &str, _visualise: bool) -> i64 {
+    fn part_2(&self, input: &str, _visualiser: &mut dyn Visualiser) -> Result<Answer, SolveError> {
         let jokers_wild = true;
         solve(input, jokers_wild)
     }
+
+    fn parse(&self, input: &str) -> Option<Result<(), SolveError>> {
+        Some(parse_lines(input, |line| parse_hand(line, false)).map(|_| ()))
+    }
 }
 
-fn solve(input: &str, jokers_wild: bool) -> i64 {
+fn solve(input: &str, jokers_wild: bool) -> Result<Answer, SolveError> {
     // parse hands
-    let mut hands = input.lines()
-        .map(|line| parse_hand(line, jokers_wild))
-        .collect::<Vec<_>>();
+    let mut hands = parse_lines(input, |line| parse_hand(line, jokers_wild))?;
 
     // order hands by rank
     hands.sort_by(|a, b| order(a, b, jokers_wild));
 
     // count winnings
-    hands.iter().enumerate()
+    Ok(hands.iter().enumerate()
         .map(|(rank, hand)| (rank + 1) as i64 * hand.bid)
-        .sum()
+        .sum::<i64>()
+        .into())
 }
 
 // -------------------------------------------------------------------------------------------------
@@ -80,14 +89,18 @@ fn compare(a: &str, b: &str, jokers_wild: bool) -> Ordering {
 // -------------------------------------------------------------------------------------------------
 // parsing
 
-fn parse_hand(line: &str, jokers_wild: bool) -> Hand {
-    let (card_str, bid_str) = line.split_once(" ").unwrap();
+fn parse_hand(line: &str, jokers_wild: bool) -> Result<Hand, SolveError> {
+    let (card_str, bid_str) = split_once(line, " ")?;
+
+    if card_str.len() != 5 || !card_str.chars().all(|c| "AKQJT98765432".contains(c)) {
+        return Err(SolveError::new(format!("Invalid hand: '{}'", card_str)));
+    }
 
     let cards = card_str.to_owned();
     let kind = determine_hand_type(&cards, jokers_wild);
-    let bid = bid_str.parse().unwrap();
+    let bid = parse_number(bid_str)?;
 
-    Hand { kind, cards, bid }
+    Ok(Hand { kind, cards, bid })
 }
 
 fn determine_hand_type(cards: &str, jokers_wild: bool) -> Type {
@@ -1,43 +1,57 @@
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
 
-use crate::solutions::Harness;
+use crate::solutions::{Answer, Harness, Solution, SolveError};
+use crate::solutions::error::split_once;
+use crate::visualise::{Graph, Shape, Visualiser};
 
 pub struct Day8 {}
 
+pub const SOLUTION: Solution = Solution {
+    day: 8, title: "Haunted Wasteland", parts: &[1, 2], new: || Box::new(Day8 {}),
+};
+
 impl Harness for Day8 {
-    fn part_1(&self, input: &str, _visualise: bool) -> i64 {
-        let navigation = parse_file(input);
-        let steps = follow_navigation(&navigation, "AAA", |n| n == "ZZZ");
-        steps
+    fn part_1(&self, input: &str, _visualiser: &mut dyn Visualiser) -> Result<Answer, SolveError> {
+        let navigation = parse_file(input)?;
+        follow_navigation(&navigation, "AAA", |n| n == "ZZZ").map(Answer::from)
     }
 
-    fn part_2(&self, input: &str, _visualise: bool) -> i64 {
-        let navigation = parse_file(input);
+    fn part_2(&self, input: &str, _visualiser: &mut dyn Visualiser) -> Result<Answer, SolveError> {
+        let navigation = parse_file(input)?;
 
         let steps_for_starts = navigation.maze.keys().cloned()
             .filter(|k| k.ends_with("A"))
             .map(|s| follow_navigation(&navigation, &s, |n| n.ends_with("Z")))
-            .collect::<Vec<_>>();
+            .collect::<Result<Vec<_>, _>>()?;
+
+        Ok(lowest_common_multiple(&steps_for_starts).into())
+    }
+
+    fn parse(&self, input: &str) -> Option<Result<(), SolveError>> {
+        Some(parse_file(input).map(|_| ()))
+    }
 
-        lowest_common_multiple(&steps_for_starts)
+    fn graph(&self, input: &str) -> Option<Result<Graph, SolveError>> {
+        Some(parse_file(input).and_then(|navigation| to_graph(&navigation)))
     }
 }
 
 // ----------------
 
-fn follow_navigation<F>(navigation: &Navigation, start: &str, finish: F) -> i64
+fn follow_navigation<F>(navigation: &Navigation, start: &str, finish: F) -> Result<i64, SolveError>
     where F: Fn(&str) -> bool
 {
     let mut pos = start;
     let mut step = 0;
 
     while !finish(pos) {
-        let options = &navigation.maze.get(pos).unwrap();
-        pos = next_step(step, &navigation.path, &options);
+        let options = navigation.maze.get(pos)
+            .ok_or_else(|| SolveError::new(format!("Unknown node: '{}'", pos)))?;
+        pos = next_step(step, &navigation.path, options);
         step += 1;
     }
 
-    step
+    Ok(step)
 }
 
 fn next_step<'a>(step: i64, path: &str, next: &'a (String, String)) -> &'a str {
@@ -46,6 +60,23 @@ fn next_step<'a>(step: i64, path: &str, next: &'a (String, String)) -> &'a str {
     if c == 'L' { &next.0 } else { &next.1 }
 }
 
+/// The steps taken from a start node to the first end node, and then on for as many steps again.
+/// Taking the lowest common multiple of the steps for each start relies on this arriving back at
+/// an end node, ie. on the route leading into a cycle of the same length.
+fn route<'a>(navigation: &'a Navigation, start: &'a str) -> Result<Vec<(&'a str, &'a str)>, SolveError> {
+    let steps = follow_navigation(navigation, start, |n| n.ends_with("Z"))?;
+
+    let mut pos = start;
+    let mut route = Vec::new();
+    for step in 0..steps * 2 {
+        let Some(options) = navigation.maze.get(pos) else { break };
+        let next = next_step(step, &navigation.path, options);
+        route.push((pos, next));
+        pos = next;
+    }
+    Ok(route)
+}
+
 /// https://www.geeksforgeeks.org/lcm-of-given-array-elements/
 fn lowest_common_multiple(input: &[i64]) -> i64 {
     let mut items = input.iter().cloned().collect::<Vec<i64>>();
@@ -84,27 +115,85 @@ struct Navigation {
     maze: HashMap<String, (String, String)>,
 }
 
+// -------------------------------------------------------------------------------------------------
+// visualisation
+
+/// The network of nodes, with the route from each start node to its end nodes highlighted
+fn to_graph(navigation: &Navigation) -> Result<Graph, SolveError> {
+    let mut names = navigation.maze.keys().collect::<Vec<_>>();
+    names.sort();
+
+    let mut graph = Graph::new();
+    for name in &names {
+        let shape = if name.ends_with('A') { Shape::Hexagon } else if name.ends_with('Z') { Shape::DoubleCircle } else { Shape::Ellipse };
+        graph.node(name, shape);
+    }
+    for name in &names {
+        let (left, right) = &navigation.maze[*name];
+        if left == right {
+            graph.labelled_edge(name, left, "L/R");
+        } else {
+            graph.labelled_edge(name, left, "L");
+            graph.labelled_edge(name, right, "R");
+        }
+    }
+
+    // routes repeat their steps and overlap, so find the distinct edges and nodes first. Nodes on
+    // more than one route are grouped with the first start to reach them.
+    let starts = names.iter().filter(|n| n.ends_with('A')).collect::<Vec<_>>();
+    let mut groups = HashMap::new();
+    let mut edges = HashSet::new();
+    for start in &starts {
+        for (from, to) in route(navigation, start)? {
+            groups.entry(from).or_insert(*start);
+            groups.entry(to).or_insert(*start);
+            edges.insert((from, to));
+        }
+    }
+
+    for (node, start) in groups {
+        graph.group(node, &format!("from {}", start));
+        if node.ends_with('Z') { graph.highlight(node); }
+    }
+    for (from, to) in edges {
+        graph.highlight_edge(from, to);
+    }
+    for start in starts {
+        graph.highlight(start);
+    }
+
+    Ok(graph)
+}
+
 // -------------------------------------------------------------------------------------------------
 // parsing
 
-fn parse_file(input: &str) -> Navigation {
+fn parse_file(input: &str) -> Result<Navigation, SolveError> {
     let mut path = String::new();
     let mut maze = HashMap::new();
 
     for (i, line) in input.lines().enumerate() {
         if i == 0 {
+            if line.is_empty() || !line.chars().all(|c| c == 'L' || c == 'R') {
+                return Err(SolveError::new(format!("Invalid path: '{}'", line)).on_line(i + 1));
+            }
             path = line.to_owned();
         } else if line.is_empty() {
             continue;
         } else {
-            let (key, next) = line.split_once("=").unwrap();
-            let options = next.trim().split_once(",")
-                .map(|(a, b)| (a[1..=3].to_owned(), b[1..=3].to_owned()))
-                .unwrap();
-
-            maze.insert(key.trim().to_owned(), options);
+            let (key, options) = parse_node(line).map_err(|e| e.on_line(i + 1))?;
+            maze.insert(key, options);
         }
     }
 
-    Navigation { path, maze }
+    Ok(Navigation { path, maze })
+}
+
+fn parse_node(line: &str) -> Result<(String, (String, String)), SolveError> {
+    let (key, next) = split_once(line, "=")?;
+    let (left, right) = split_once(next, ",")?;
+
+    let name = |n: &str| n.trim_matches(|c: char| c.is_whitespace() || c == '(' || c == ')').to_owned();
+
+    Ok((key.trim().to_owned(), (name(left), name(right))))
 }
\ No newline at end of file
@@ -1,32 +1,34 @@
-use crate::solutions::Harness;
+use crate::solutions::{Answer, Harness, Solution, SolveError};
+use crate::solutions::error::{parse_lines, parse_number};
+use crate::visualise::Visualiser;
 
 pub struct Day9 {}
 
+pub const SOLUTION: Solution = Solution {
+    day: 9, title: "Mirage Maintenance", parts: &[1, 2], new: || Box::new(Day9 {}),
+};
+
 impl Harness for Day9 {
-    fn part_1(&self, input: &str, _visualise: bool) -> i64 {
+    fn part_1(&self, input: &str, _visualiser: &mut dyn Visualiser) -> Result<Answer, SolveError> {
         let mut sum = 0;
-        for line in input.lines() {
-            let seq = line.split_whitespace()
-                .map(|n| n.parse().unwrap())
-                .collect::<Vec<i64>>();
-
+        for seq in parse_lines(input, parse_sequence)? {
             let (_, next) = solve(seq);
             sum += next;
         }
-        sum
+        Ok(sum.into())
     }
 
-    fn part_2(&self, input: &str, _visualise: bool) -> i64 {
+    fn part_2(&self, input: &str, _visualiser: &mut dyn Visualiser) -> Result<Answer, SolveError> {
         let mut sum = 0;
-        for line in input.lines() {
-            let seq = line.split_whitespace()
-                .map(|n| n.parse().unwrap())
-                .collect::<Vec<i64>>();
-
+        for seq in parse_lines(input, parse_sequence)? {
             let (prev, _) = solve(seq);
             sum += prev;
         }
-        sum
+        Ok(sum.into())
+    }
+
+    fn parse(&self, input: &str) -> Option<Result<(), SolveError>> {
+        Some(parse_lines(input, parse_sequence).map(|_| ()))
     }
 }
 
@@ -44,4 +46,13 @@ fn solve(seq: Vec<i64>) -> (i64, i64) {
     let (prev, next) = solve(diffs);
 
     (seq[0] - prev, seq[seq.len() - 1] + next)
-}
\ No newline at end of file
+}
+
+// -------------------------------------------------------------------------------------------------
+// parsing
+
+fn parse_sequence(line: &str) -> Result<Vec<i64>, SolveError> {
+    line.split_whitespace()
+        .map(parse_number)
+        .collect()
+}
@@ -0,0 +1,123 @@
+use std::error::Error;
+use std::fmt::{Display, Formatter};
+use std::str::FromStr;
+
+use crate::utils::grid::GridError;
+
+/// Error raised when a solution can't be calculated, usually because the input is malformed
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct SolveError {
+    /// day of the challenge being solved
+    pub day: Option<u8>,
+    /// which part of the challenge was being solved
+    pub part: Option<u8>,
+    /// line of the input which caused the problem (1-based)
+    pub line: Option<usize>,
+    /// what went wrong
+    pub reason: String,
+}
+
+impl SolveError {
+    pub fn new<S: Into<String>>(reason: S) -> SolveError {
+        SolveError { day: None, part: None, line: None, reason: reason.into() }
+    }
+
+    /// Record which line of the input caused this error, unless it's already known
+    pub fn on_line(mut self, line: usize) -> SolveError {
+        self.line.get_or_insert(line);
+        self
+    }
+
+    /// Record which day and part were being solved when this error occurred
+    pub fn in_part(mut self, day: u8, part: u8) -> SolveError {
+        self.day = Some(day);
+        self.part = Some(part);
+        self
+    }
+
+    /// Record which day was being solved when this error occurred, eg. while parsing its input
+    pub fn in_day(mut self, day: u8) -> SolveError {
+        self.day = Some(day);
+        self
+    }
+}
+
+impl Display for SolveError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        if let Some(day) = self.day { write!(f, "Day {}, ", day)?; }
+        if let Some(part) = self.part { write!(f, "Part {}, ", part)?; }
+        if let Some(line) = self.line { write!(f, "line {}: ", line)?; }
+        write!(f, "{}", self.reason)
+    }
+}
+
+impl Error for SolveError {}
+
+/// Problems with a grid are reported on the line of the input they were found
+impl<E: Display> From<GridError<E>> for SolveError {
+    fn from(error: GridError<E>) -> SolveError {
+        match error {
+            GridError::Empty => SolveError::new("Empty grid"),
+            GridError::Ragged { row, width, expected } =>
+                SolveError::new(format!("Expected {} characters but found {}", expected, width)).on_line(row),
+            GridError::Cell { row, column, error } =>
+                SolveError::new(format!("{} at column {}", error, column)).on_line(row),
+        }
+    }
+}
+
+// -------------------------------------------------------------------------------------------------
+// parsing helpers
+
+/// Parse each line of the input in turn, tagging any errors with the line number
+pub fn parse_lines<T, F>(input: &str, parse: F) -> Result<Vec<T>, SolveError>
+    where F: Fn(&str) -> Result<T, SolveError>
+{
+    input.lines().enumerate()
+        .map(|(i, line)| parse(line).map_err(|e| e.on_line(i + 1)))
+        .collect()
+}
+
+/// Parse a single (trimmed) number
+pub fn parse_number<T: FromStr>(value: &str) -> Result<T, SolveError> {
+    value.trim().parse()
+        .map_err(|_| SolveError::new(format!("Invalid number: '{}'", value.trim())))
+}
+
+/// Split a value into two at the first occurrence of the delimiter
+pub fn split_once<'a>(value: &'a str, delimiter: &str) -> Result<(&'a str, &'a str), SolveError> {
+    value.split_once(delimiter)
+        .ok_or_else(|| SolveError::new(format!("Expected '{}' in: '{}'", delimiter, value)))
+}
+
+// -------------------------------------------------------------------------------------------------
+// tests
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn display() {
+        let error = SolveError::new("Invalid number: 'x'");
+        assert_eq!("Invalid number: 'x'", error.to_string());
+
+        let error = error.on_line(12).in_part(5, 2);
+        assert_eq!("Day 5, Part 2, line 12: Invalid number: 'x'", error.to_string());
+    }
+
+    #[test]
+    fn grid_errors() {
+        let error = SolveError::from(GridError::Cell { row: 3, column: 7, error: "Unknown symbol 'x'" });
+        assert_eq!("line 3: Unknown symbol 'x' at column 7", error.to_string());
+
+        let error = SolveError::from(GridError::<String>::Ragged { row: 2, width: 4, expected: 5 });
+        assert_eq!("line 2: Expected 5 characters but found 4", error.to_string());
+    }
+
+    #[test]
+    fn parse_lines_tags_line_number() {
+        let result = parse_lines::<i64, _>("1\n2\nthree\n4", parse_number);
+        assert_eq!(Some(3), result.unwrap_err().line);
+    }
+}
@@ -1,59 +1,79 @@
-mod day1;
-mod day2;
-mod day3;
-mod day4;
-mod day5;
-mod day6;
-mod day7;
-mod day8;
-mod day9;
-mod day10;
-mod day11;
-mod day12;
-mod day13;
-mod day14;
-mod day15;
-mod day16;
-mod day17;
-mod day18;
-mod day19;
-mod day20;
-mod day21;
+/// Declare the modules for each day, and register their solutions. Each module provides a
+/// `SOLUTION` describing itself, so adding a day just means adding its module to this list.
+macro_rules! solutions {
+    ($($module:ident),* $(,)?) => {
+        $(mod $module;)*
+
+        const SOLUTIONS: &[Solution] = &[$($module::SOLUTION),*];
+    };
+}
+
+solutions!(
+    day1, day2, day3, day4, day5, day6, day7, day8, day9, day10, day11, day12, day13,
+    day14, day15, day16, day17, day18, day19, day20, day21, day22, day23, day24, day25,
+);
+
+mod answer;
+mod error;
+
+pub use answer::Answer;
+pub use error::SolveError;
+
+use crate::visualise::{Graph, Visualiser};
 
 /// Common structure for solution to a day's challenge
 pub trait Harness {
     /// Calculate part 1 answer for the given input
-    fn part_1(&self, input: &str, visualise: bool) -> i64;
+    fn part_1(&self, input: &str, visualiser: &mut dyn Visualiser) -> Result<Answer, SolveError>;
 
     /// Calculate part 2 answer for the given input
-    fn part_2(&self, input: &str, visualise: bool) -> i64;
-}
+    fn part_2(&self, input: &str, visualiser: &mut dyn Visualiser) -> Result<Answer, SolveError>;
 
+    /// Parse the input without calculating anything, so that parsing can be timed on its own.
+    /// Returns `None` for days which don't have a separate parsing step.
+    fn parse(&self, _input: &str) -> Option<Result<(), SolveError>> {
+        None
+    }
 
-/// Get the solution for a specific day
-pub fn get_solution(day: u8) -> Box<dyn Harness> {
-    match day {
-        1 => Box::new(day1::Day1 {}),
-        2 => Box::new(day2::Day2 {}),
-        3 => Box::new(day3::Day3 {}),
-        4 => Box::new(day4::Day4 {}),
-        5 => Box::new(day5::Day5 {}),
-        6 => Box::new(day6::Day6 {}),
-        7 => Box::new(day7::Day7 {}),
-        8 => Box::new(day8::Day8 {}),
-        9 => Box::new(day9::Day9 {}),
-        10 => Box::new(day10::Day10 {}),
-        11 => Box::new(day11::Day11 {}),
-        12 => Box::new(day12::Day12 {}),
-        13 => Box::new(day13::Day13 {}),
-        14 => Box::new(day14::Day14 {}),
-        15 => Box::new(day15::Day15 {}),
-        16 => Box::new(day16::Day16 {}),
-        17 => Box::new(day17::Day17 {}),
-        18 => Box::new(day18::Day18 {}),
-        19 => Box::new(day19::Day19 {}),
-        20 => Box::new(day20::Day20 {}),
-        21 => Box::new(day21::Day21 {}),
-        _ => panic!("Day {} not available", day)
+    /// Describe the input as a graph, for days where it's a network of some kind.
+    /// Returns `None` for days which don't have one.
+    fn graph(&self, _input: &str) -> Option<Result<Graph, SolveError>> {
+        None
     }
 }
+
+
+/// Entry in the registry of solutions
+pub struct Solution {
+    pub day: u8,
+    /// title of the day's challenge
+    pub title: &'static str,
+    /// which parts of the challenge have been solved
+    pub parts: &'static [u8],
+    /// create the solution
+    pub new: fn() -> Box<dyn Harness>,
+}
+
+/// All the registered solutions, in order of day
+pub fn list() -> &'static [Solution] {
+    SOLUTIONS
+}
+
+/// Get the list of days which have solutions available
+pub fn available_days() -> Vec<u8> {
+    SOLUTIONS.iter().map(|s| s.day).collect()
+}
+
+/// Get which parts of a specific day have been solved, if there's a solution for it
+pub fn solved_parts(day: u8) -> Option<&'static [u8]> {
+    SOLUTIONS.iter()
+        .find(|s| s.day == day)
+        .map(|s| s.parts)
+}
+
+/// Get the solution for a specific day, if there is one
+pub fn get_solution(day: u8) -> Option<Box<dyn Harness>> {
+    SOLUTIONS.iter()
+        .find(|s| s.day == day)
+        .map(|s| (s.new)())
+}
@@ -1,23 +1,91 @@
 // -------------------------------------------------------------------------------------------------
 // Grid
 
-use crate::utils::point::{Bounds, Direction, Point};
+use std::convert::Infallible;
+use std::fmt::{Display, Formatter};
+use std::str::FromStr;
 
-/// A 2D array-based grid, it starts at `(0,0)` and extends only in positive `x` and `y` directions
+use crate::utils::point::{Bounds, Connectivity, Direction, Point};
+use crate::visualise::{Frame, Style};
+
+/// Something made up of cells on a 2D grid, which can be looked up and changed by their points
+pub trait Cells<T> {
+    /// Get the value at the given point, if there is one
+    fn get(&self, p: &Point) -> Option<&T>;
+
+    /// Set the value at the given point, if it's a point this can hold
+    fn set(&mut self, p: &Point, value: T);
+
+    /// Get the neighbours of the given point, with or without diagonals, which are part of the
+    /// grid
+    fn neighbours(&self, p: &Point, connectivity: Connectivity) -> Vec<Point>;
+
+    /// Get all points which are directly adjacent to the given point (doesn't include diagonals),
+    /// and which are part of the grid
+    fn adjacent(&self, p: &Point) -> Vec<Point> {
+        self.neighbours(p, Connectivity::Four)
+    }
+
+    /// The area covered by the cells
+    fn bounds(&self) -> Bounds;
+}
+
+/// A 2D array-based grid, it starts at `(0,0)` and extends only in positive `x` and `y` directions.
+/// The cells are stored row by row in a single `Vec`, so each one can also be found by its index.
 #[derive(Clone)]
 pub struct Grid<T> {
     bounds: Bounds,
-    cells: Vec<Vec<T>>,
+    width: usize,
+    cells: Vec<T>,
 }
 
 impl<T> Grid<T> {
-    /// Construct a new grid from the given rows
-    pub fn new<A>(cells: Vec<Vec<A>>) -> Grid<A> {
-        let height = cells.len() as i64;
-        let width = cells[0].len() as i64;
-        let bounds = Bounds { min: Point::new(0, 0), max: Point::new(width - 1, height - 1) };
+    /// Construct a new grid from the given rows. Its width is that of the first row, or zero if
+    /// there aren't any.
+    ///
+    /// Panics if the rows aren't all the same width, use [`Grid::parse`] to check input.
+    pub fn new<A>(rows: Vec<Vec<A>>) -> Grid<A> {
+        let height = rows.len();
+        let width = rows.first().map_or(0, |row| row.len());
+        assert!(rows.iter().all(|row| row.len() == width), "Every row of a grid must be the same width");
+
+        Grid::<A>::from_cells(width, height, rows.into_iter().flatten().collect())
+    }
+
+    /// Construct a grid of the given size from its cells, row by row
+    fn from_cells(width: usize, height: usize, cells: Vec<T>) -> Grid<T> {
+        let bounds = Bounds { min: Point::new(0, 0), max: Point::new(width as i64 - 1, height as i64 - 1) };
+        Grid { bounds, width, cells }
+    }
+
+    /// Read a grid from lines of text, using the given function to turn each character into a
+    /// value. Every line must be the same width.
+    pub fn parse<F, E>(input: &str, parse: F) -> Result<Grid<T>, GridError<E>>
+        where F: Fn(char, &Point) -> Result<T, E>
+    {
+        let mut cells = Vec::with_capacity(input.len());
+        let mut width = None;
+        let mut height = 0;
+        for (y, line) in input.lines().enumerate() {
+            let start = cells.len();
+            for (x, c) in line.chars().enumerate() {
+                let value = parse(c, &Point::new(x as i64, y as i64))
+                    .map_err(|error| GridError::Cell { row: y + 1, column: x + 1, error })?;
+                cells.push(value);
+            }
+
+            let row = cells.len() - start;
+            let expected = *width.get_or_insert(row);
+            if row != expected {
+                return Err(GridError::Ragged { row: y + 1, width: row, expected });
+            }
+            height += 1;
+        }
 
-        Grid { bounds, cells }
+        match width {
+            Some(width) if width > 0 => Ok(Grid::from_cells(width, height, cells)),
+            _ => Err(GridError::Empty),
+        }
     }
 
     pub fn height(&self) -> i64 {
@@ -36,19 +104,32 @@ impl<T> Grid<T> {
 
     /// Get the value at the given point, if it's within the grid bounds
     pub fn get(&self, p: &Point) -> Option<&T> {
-        self.cells.get(p.y as usize)
-            .and_then(|row| row.get(p.x as usize))
+        self.point_to_idx(p).map(|i| &self.cells[i])
     }
 
     /// Set the value at the given point, if it's within the grid bounds
     pub fn set(&mut self, p: &Point, value: T) {
-        if let Some(row) = self.cells.get_mut(p.y as usize) {
-            if let Some(v) = row.get_mut(p.x as usize) {
-                *v = value;
-            }
+        if let Some(i) = self.point_to_idx(p) {
+            self.cells[i] = value;
         }
     }
 
+    /// Get the value at the given index (counting row by row), if it's within the grid
+    pub fn get_idx(&self, idx: usize) -> Option<&T> {
+        self.cells.get(idx)
+    }
+
+    /// The index of the given point (counting row by row), if it's within the grid bounds
+    pub fn point_to_idx(&self, p: &Point) -> Option<usize> {
+        if p.x < 0 || p.y < 0 || p.x >= self.width() || p.y >= self.height() { return None; }
+        Some(p.y as usize * self.width + p.x as usize)
+    }
+
+    /// The point at the given index (counting row by row)
+    pub fn idx_to_point(&self, idx: usize) -> Point {
+        Point::new((idx % self.width) as i64, (idx / self.width) as i64)
+    }
+
     /// Get an iterator over all the Points in the grid (row-wise)
     pub fn points(&self) -> ByRowIterator<T> {
         ByRowIterator { grid: self, current: None }
@@ -61,15 +142,16 @@ impl<T> Grid<T> {
 
     /// Iterating row-wise, find the first point where the given predicate is true
     pub fn find_first<P: Fn(&T) -> bool>(&self, predicate: P) -> Option<Point> {
-        self.points()
-            .filter(|p| self.get(p).filter(|v| predicate(*v)).is_some())
-            .next()
+        (0..self.cells.len())
+            .find(|&i| self.get_idx(i).is_some_and(&predicate))
+            .map(|i| self.idx_to_point(i))
     }
 
     /// Find all points where the given predicate is true
     pub fn find_all<P: Fn(&T) -> bool>(&self, predicate: P) -> Vec<Point> {
-        self.points()
-            .filter(|p| self.get(p).filter(|v| predicate(*v)).is_some())
+        (0..self.cells.len())
+            .filter(|&i| self.get_idx(i).is_some_and(&predicate))
+            .map(|i| self.idx_to_point(i))
             .collect()
     }
 
@@ -84,29 +166,155 @@ impl<T> Grid<T> {
         p.travel_bounded(direction, self.bounds())
     }
 
-    /// Get the point which is one position to the diagonal of the given point
-    pub fn travel_diag(&self, p: &Point, d1: Direction, d2: Direction) -> Option<Point> {
-        self.travel(p, d1)
-            .and_then(|n| self.travel(&n, d2))
-    }
-
     /// Get all points which are directly adjacent to the given point (doesn't include diagonals)
     pub fn adjacent(&self, p: &Point) -> Vec<Point> {
         p.adjacent_bounded(self.bounds())
     }
 
-    /// Display the grid, using the given function to decide what to show for each cell's value
-    pub fn visualise<F, S>(&self, to_str: F)
+    /// Get all eight points around the given point, including diagonals
+    pub fn neighbours8(&self, p: &Point) -> Vec<Point> {
+        self.neighbours(p, Connectivity::Eight)
+    }
+
+    /// Get the neighbours of the given point within the grid, with or without diagonals
+    pub fn neighbours(&self, p: &Point, connectivity: Connectivity) -> Vec<Point> {
+        p.neighbours_bounded(connectivity, self.bounds())
+    }
+
+    /// Draw the grid, using the given function to decide what to show for each cell's value
+    pub fn visualise<F, S>(&self, to_str: F) -> Frame
         where S: AsRef<str>, F: Fn(&T, &Point) -> S
     {
+        let rows = (0..self.height())
+            .map(|y| (0..self.width())
+                .map(|x| {
+                    let p = Point::new(x, y);
+                    self.get(&p).map(|v| to_str(v, &p).as_ref().to_owned()).unwrap_or_else(|| " ".to_owned())
+                })
+                .collect())
+            .collect();
+
+        Frame::new(rows)
+    }
+
+    /// Draw the grid in styles, using the given function to decide what to show for each cell's
+    /// value and how it should look
+    pub fn visualise_styled<F, S>(&self, to_cell: F) -> Frame
+        where S: AsRef<str>, F: Fn(&T, &Point) -> (S, Style)
+    {
+        let mut rows = Vec::new();
+        let mut styles = Vec::new();
         for y in 0..self.height() {
+            let mut row = String::new();
+            let mut row_styles = Vec::new();
             for x in 0..self.width() {
                 let p = Point::new(x, y);
                 if let Some(v) = self.get(&p) {
-                    print!("{}", to_str(v, &p).as_ref());
-                } else { print!(" "); }
+                    let (text, style) = to_cell(v, &p);
+                    row.push_str(text.as_ref());
+                    row_styles.extend(std::iter::repeat_n(style, text.as_ref().chars().count()));
+                }
             }
-            println!();
+            rows.push(row);
+            styles.push(row_styles);
+        }
+
+        Frame::styled(rows, styles)
+    }
+}
+
+impl<T> Cells<T> for Grid<T> {
+    fn get(&self, p: &Point) -> Option<&T> {
+        Grid::get(self, p)
+    }
+
+    fn set(&mut self, p: &Point, value: T) {
+        Grid::set(self, p, value)
+    }
+
+    fn neighbours(&self, p: &Point, connectivity: Connectivity) -> Vec<Point> {
+        Grid::neighbours(self, p, connectivity)
+    }
+
+    fn bounds(&self) -> Bounds {
+        Grid::bounds(self).clone()
+    }
+}
+
+// -------------------------------------------------------------------------------------------------
+// tiling
+
+/// A grid which repeats infinitely in every direction, by wrapping points onto a base grid
+#[derive(Clone)]
+pub struct TiledGrid<T> {
+    base: Grid<T>,
+}
+
+impl<T> TiledGrid<T> {
+    pub fn new(base: Grid<T>) -> TiledGrid<T> {
+        TiledGrid { base }
+    }
+
+    /// The grid which is repeated
+    pub fn base(&self) -> &Grid<T> {
+        &self.base
+    }
+
+    /// Map a point onto the base grid
+    pub fn wrap(&self, p: &Point) -> Point {
+        Point::new(p.x.rem_euclid(self.base.width()), p.y.rem_euclid(self.base.height()))
+    }
+}
+
+impl<T> Cells<T> for TiledGrid<T> {
+    fn get(&self, p: &Point) -> Option<&T> {
+        self.base.get(&self.wrap(p))
+    }
+
+    /// Setting a value changes it in every tile
+    fn set(&mut self, p: &Point, value: T) {
+        let p = self.wrap(p);
+        self.base.set(&p, value);
+    }
+
+    /// Every point has all of its neighbours, since the grid never ends
+    fn neighbours(&self, p: &Point, connectivity: Connectivity) -> Vec<Point> {
+        p.neighbours(connectivity)
+    }
+
+    /// The bounds of a single tile, the one which starts at `(0,0)`
+    fn bounds(&self) -> Bounds {
+        self.base.bounds().clone()
+    }
+}
+
+// -------------------------------------------------------------------------------------------------
+
+/// Any text can be read as a grid of characters, as long as its lines are all the same width
+impl FromStr for Grid<char> {
+    type Err = GridError<Infallible>;
+
+    fn from_str(input: &str) -> Result<Self, Self::Err> {
+        Grid::parse(input, |c, _| Ok(c))
+    }
+}
+
+/// Problem reading a grid from text. Rows and columns are counted from 1.
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub enum GridError<E> {
+    Empty,
+    /// a row isn't as wide as the first one
+    Ragged { row: usize, width: usize, expected: usize },
+    /// a character couldn't be turned into a value
+    Cell { row: usize, column: usize, error: E },
+}
+
+impl<E: Display> Display for GridError<E> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            GridError::Empty => write!(f, "Empty grid"),
+            GridError::Ragged { row, width, expected } => write!(f, "Row {} is {} wide, expected {}", row, width, expected),
+            GridError::Cell { row, column, error } => write!(f, "{} at row {}, column {}", error, row, column),
         }
     }
 }
@@ -130,8 +338,9 @@ impl<'a, T> Iterator for ByRowIterator<'a, T> {
                 next.x = 0;
                 next.y += 1;
             }
-            if next.y >= self.grid.height() { return None; }
         }
+        // checked for the first point too, in case the grid is empty
+        if next.y >= self.grid.height() || next.x >= self.grid.width() { return None; }
         self.current = Some(next);
         self.current
     }
@@ -161,8 +370,8 @@ impl<'a, T> Iterator for ByColumnIterator<'a, T> {
                 next.y = 0;
                 next.x += 1;
             }
-            if next.x >= self.grid.width() { return None; }
         }
+        if next.x >= self.grid.width() || next.y >= self.grid.height() { return None; }
         self.current = Some(next);
         self.current
     }
@@ -240,4 +449,73 @@ mod tests {
             Point::new(2, 0), Point::new(2, 1), Point::new(2, 2), Point::new(2, 3),
         ])
     }
-}
\ No newline at end of file
+
+    #[test]
+    fn parse_grid() {
+        let grid = Grid::parse("12\n34\n", |c, p| c.to_digit(10).map(|d| d as i64 + p.y).ok_or(c)).unwrap();
+        assert_eq!((2, 2), (grid.width(), grid.height()));
+        assert_eq!(Some(&5), grid.get(&Point::new(1, 1)));
+
+        let error = Grid::parse("12\n3x", |c, _| c.to_digit(10).ok_or(c));
+        assert_eq!(Err(GridError::Cell { row: 2, column: 2, error: 'x' }), error.map(|_| ()));
+
+        let error = "..\n.\n..".parse::<Grid<char>>();
+        assert_eq!(Err(GridError::Ragged { row: 2, width: 1, expected: 2 }), error.map(|_| ()));
+        assert_eq!(Err(GridError::Empty), "".parse::<Grid<char>>().map(|_| ()));
+        assert_eq!(Err(GridError::Empty), "\n".parse::<Grid<char>>().map(|_| ()));
+    }
+
+    #[test]
+    fn index_points() {
+        let grid = Grid::<i32>::new(vec![vec![0, 1, 2], vec![3, 4, 5]]);
+
+        assert_eq!(Some(4), grid.point_to_idx(&Point::new(1, 1)));
+        assert_eq!(Point::new(1, 1), grid.idx_to_point(4));
+        assert_eq!(Some(&5), grid.get_idx(5));
+        assert_eq!(None, grid.get_idx(6));
+        for p in [Point::new(3, 0), Point::new(-1, 1), Point::new(0, 2), Point::new(0, -1)] {
+            assert_eq!(None, grid.point_to_idx(&p));
+            assert_eq!(None, grid.get(&p));
+        }
+        assert_eq!(vec![Point::new(2, 0), Point::new(0, 1)], grid.find_all(|v| *v == 2 || *v == 3));
+    }
+
+    #[test]
+    #[should_panic(expected = "same width")]
+    fn new_ragged_grid() {
+        Grid::<i32>::new(vec![vec![0, 1], vec![2]]);
+    }
+
+    #[test]
+    fn new_empty_grid() {
+        let grid = Grid::<i32>::new(Vec::<Vec<i32>>::new());
+        assert_eq!((0, 0), (grid.width(), grid.height()));
+        assert_eq!(None, grid.get(&Point::new(0, 0)));
+        assert_eq!(0, grid.points().count());
+        assert_eq!(0, grid.points_by_column().count());
+    }
+
+    #[test]
+    fn tiled_grid() {
+        let mut tiled = TiledGrid::new(Grid::<i32>::new(vec![vec![0, 1, 2], vec![3, 4, 5]]));
+
+        assert_eq!(Some(&4), tiled.get(&Point::new(1, 1)));
+        assert_eq!(Some(&5), tiled.get(&Point::new(-1, -1)));
+        assert_eq!(Some(&0), tiled.get(&Point::new(6, -4)));
+        assert_eq!(4, tiled.adjacent(&Point::new(0, 0)).len());
+
+        tiled.set(&Point::new(-2, 3), 9);
+        assert_eq!(Some(&9), tiled.base().get(&Point::new(1, 1)));
+        assert_eq!(Bounds { min: Point::new(0, 0), max: Point::new(2, 1) }, tiled.bounds());
+    }
+
+    #[test]
+    fn visualise_styled() {
+        let grid = Grid::<i32>::new(vec![vec![1, 22], vec![3, 4]]);
+        let bold = Style::new().bold();
+
+        let frame = grid.visualise_styled(|v, p| (v.to_string(), if p.x == 1 { bold } else { Style::new() }));
+        assert_eq!(vec!["122".to_owned(), "34".to_owned()], frame.rows);
+        assert_eq!(vec![vec![Style::new(), bold, bold], vec![Style::new(), bold]], frame.styles);
+    }
+}
@@ -1,3 +1,5 @@
 pub mod point;
 pub mod grid;
-pub mod path;
\ No newline at end of file
+pub mod path;
+pub mod sparse;
+pub mod search;
\ No newline at end of file
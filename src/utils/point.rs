@@ -40,44 +40,98 @@ impl Point {
 
     /// Get all points which are directly adjacent to the given point (doesn't include diagonals)
     pub fn adjacent(&self) -> Vec<Point> {
-        [North, South, East, West].into_iter()
+        Direction::CARDINAL.into_iter()
             .map(|d| self.travel(d))
             .collect()
     }
 
     pub fn adjacent_bounded(&self, bounds: &Bounds) -> Vec<Point> {
-        [North, South, East, West].into_iter()
+        Direction::CARDINAL.into_iter()
             .filter_map(|d| self.travel_bounded(d, bounds))
             .collect()
     }
-}// -------------------------------------------------------------------------------------------------
+
+    /// Get all eight points around the given point, including diagonals
+    pub fn neighbours8(&self) -> Vec<Point> {
+        Direction::ALL.into_iter()
+            .map(|d| self.travel(d))
+            .collect()
+    }
+
+    /// Get the neighbours of the given point, with or without diagonals
+    pub fn neighbours(&self, connectivity: Connectivity) -> Vec<Point> {
+        match connectivity {
+            Connectivity::Four => self.adjacent(),
+            Connectivity::Eight => self.neighbours8(),
+        }
+    }
+
+    pub fn neighbours_bounded(&self, connectivity: Connectivity, bounds: &Bounds) -> Vec<Point> {
+        match connectivity {
+            Connectivity::Four => self.adjacent_bounded(bounds),
+            Connectivity::Eight => Direction::ALL.into_iter()
+                .filter_map(|d| self.travel_bounded(d, bounds))
+                .collect(),
+        }
+    }
+}
+
+/// Which of the points around a point count as its neighbours
+#[derive(Debug, Eq, PartialEq, Copy, Clone)]
+pub enum Connectivity {
+    /// the four points directly above, below and to either side
+    Four,
+    /// the four adjacent points, and the four diagonals
+    Eight,
+}
+
+// -------------------------------------------------------------------------------------------------
 // directions
 
-/// A direction on a 2D grid
+/// A direction on a 2D grid: one of the four cardinal directions, or one of the four diagonals
+/// between them
 #[derive(Debug, Hash, Eq, PartialEq, Ord, PartialOrd, Copy, Clone)]
 pub enum Direction {
     North,
     South,
     East,
     West,
+    NorthEast,
+    SouthEast,
+    SouthWest,
+    NorthWest,
 }
 
 impl Direction {
+    /// North, south, east and west
+    pub const CARDINAL: [Direction; 4] = [North, South, East, West];
+    /// All eight directions, clockwise from north
+    pub const ALL: [Direction; 8] = [North, NorthEast, East, SouthEast, South, SouthWest, West, NorthWest];
+
     pub fn invert(&self) -> Direction {
         match self {
             North => South,
             East => West,
             South => North,
             West => East,
+            NorthEast => SouthWest,
+            SouthEast => NorthWest,
+            SouthWest => NorthEast,
+            NorthWest => SouthEast,
         }
     }
 
+    /// Turn a quarter turn clockwise
     pub fn rotate_clockwise(&self) -> Direction {
         match self {
             North => East,
             East => South,
             South => West,
             West => North,
+            NorthEast => SouthEast,
+            SouthEast => SouthWest,
+            SouthWest => NorthWest,
+            NorthWest => NorthEast,
         }
     }
 
@@ -110,7 +164,11 @@ impl Vector {
             North => Vector::new(0, -1 * distance),
             East => Vector::new(distance, 0),
             South => Vector::new(0, distance),
-            West => Vector::new(-1 * distance, 0)
+            West => Vector::new(-1 * distance, 0),
+            NorthEast => Vector::new(distance, -distance),
+            SouthEast => Vector::new(distance, distance),
+            SouthWest => Vector::new(-distance, distance),
+            NorthWest => Vector::new(-distance, -distance),
         }
     }
 }
@@ -166,4 +224,22 @@ mod tests {
         assert_eq!(Point::new(0, 1), point.travel(South));
         assert_eq!(Point::new(-1, 0), point.travel(West));
     }
+
+    #[test]
+    fn diagonals() {
+        let point = Point::new(3, 4);
+        assert_eq!(Point::new(4, 3), point.travel(NorthEast));
+        assert_eq!(Point::new(2, 5), point.travel(SouthWest));
+        assert_eq!(SouthEast, NorthEast.rotate_clockwise());
+        assert_eq!(NorthWest, SouthEast.invert());
+        assert_eq!(NorthEast, NorthWest.rotate_anticlockwise().invert());
+
+        let neighbours = point.neighbours8();
+        assert_eq!(8, neighbours.len());
+        assert!(neighbours.contains(&Point::new(2, 3)));
+        assert!(!neighbours.contains(&point));
+
+        let bounds = Bounds { min: Point::new(0, 0), max: Point::new(3, 4) };
+        assert_eq!(3, point.neighbours_bounded(Connectivity::Eight, &bounds).len());
+    }
 }
@@ -0,0 +1,265 @@
+// -------------------------------------------------------------------------------------------------
+// Searches over any kind of state, given a function to find the states next to each one
+
+use std::cmp::Ordering;
+use std::collections::{BinaryHeap, HashMap, HashSet, VecDeque};
+use std::hash::Hash;
+
+use crate::utils::grid::Cells;
+use crate::utils::point::{Connectivity, Point};
+
+/// What a search found: the distance to each state it reached, and the state each one was reached
+/// from, so the path to any of them can be rebuilt
+#[derive(Debug, Clone)]
+pub struct Search<S> {
+    /// each state reached, with its distance and the state it was reached from (unless it's one
+    /// of the starts)
+    pub reached: HashMap<S, (i64, Option<S>)>,
+    /// the goal state, if the search stopped at one
+    pub goal: Option<S>,
+}
+
+impl<S: Clone + Eq + Hash> Search<S> {
+    fn new() -> Search<S> {
+        Search { reached: HashMap::new(), goal: None }
+    }
+
+    pub fn distance(&self, state: &S) -> Option<i64> {
+        self.reached.get(state).map(|(distance, _)| *distance)
+    }
+
+    /// Every state reached, and its distance
+    pub fn distances(&self) -> impl Iterator<Item = (&S, i64)> {
+        self.reached.iter().map(|(state, (distance, _))| (state, *distance))
+    }
+
+    /// The states on the way to the given one, starting from the start state it was reached from
+    pub fn path_to(&self, state: &S) -> Option<Vec<S>> {
+        let mut previous = &self.reached.get(state)?.1;
+
+        let mut path = vec![state.clone()];
+        while let Some(prev) = previous {
+            path.push(prev.clone());
+            previous = &self.reached[prev].1;
+        }
+        path.reverse();
+        Some(path)
+    }
+}
+
+/// Breadth first search from the start states, where each step costs 1. Every state which can be
+/// reached is visited.
+pub fn bfs<S, N, I>(starts: impl IntoIterator<Item = S>, mut neighbours: N) -> Search<S>
+    where S: Clone + Eq + Hash, N: FnMut(&S) -> I, I: IntoIterator<Item = S>
+{
+    let mut search = Search::new();
+    let mut queue = VecDeque::new();
+    for start in starts {
+        if !search.reached.contains_key(&start) {
+            search.reached.insert(start.clone(), (0, None));
+            queue.push_back((start, 0));
+        }
+    }
+
+    while let Some((state, distance)) = queue.pop_front() {
+        for next in neighbours(&state) {
+            if search.reached.contains_key(&next) { continue; }
+
+            search.reached.insert(next.clone(), (distance + 1, Some(state.clone())));
+            queue.push_back((next, distance + 1));
+        }
+    }
+    search
+}
+
+/// Dijkstra search from the start states, stopping at the first state which is a `goal`, if
+/// there is one. `cost` is the cost of moving from a state to one of its neighbours.
+pub fn dijkstra<S, N, I, C, G>(starts: impl IntoIterator<Item = S>, neighbours: N, cost: C, goal: G) -> Search<S>
+    where S: Clone + Eq + Hash, N: FnMut(&S) -> I, I: IntoIterator<Item = S>,
+          C: FnMut(&S, &S) -> i64, G: FnMut(&S) -> bool
+{
+    a_star(starts, neighbours, cost, |_| 0, goal)
+}
+
+/// A* search from the start states, stopping at the first state which is a `goal`, if there is
+/// one. The `heuristic` estimates the remaining cost to a goal. It must never overestimate it, or
+/// drop by more than the cost of moving from one state to the next.
+///
+/// If the search stops at a goal, only the states which were searched before it have distances.
+pub fn a_star<S, N, I, C, H, G>(starts: impl IntoIterator<Item = S>, mut neighbours: N, mut cost: C,
+                                 mut heuristic: H, mut goal: G) -> Search<S>
+    where S: Clone + Eq + Hash, N: FnMut(&S) -> I, I: IntoIterator<Item = S>,
+          C: FnMut(&S, &S) -> i64, H: FnMut(&S) -> i64, G: FnMut(&S) -> bool
+{
+    let mut search = Search::new();
+    let mut queue = starts.into_iter()
+        .map(|start| Queued { estimate: heuristic(&start), distance: 0, state: start, previous: None })
+        .collect::<BinaryHeap<_>>();
+
+    while let Some(Queued { distance, state, previous, .. }) = queue.pop() {
+        // the first time a state comes out of the queue is the cheapest way to reach it
+        if search.reached.contains_key(&state) { continue; }
+        search.reached.insert(state.clone(), (distance, previous));
+
+        if goal(&state) {
+            search.goal = Some(state);
+            break;
+        }
+
+        for next in neighbours(&state) {
+            let distance = distance + cost(&state, &next);
+            let estimate = distance + heuristic(&next);
+            queue.push(Queued { estimate, distance, state: next, previous: Some(state.clone()) });
+        }
+    }
+    search
+}
+
+/// A state waiting to be searched, ordered so the lowest estimate comes out of the queue first
+struct Queued<S> {
+    estimate: i64,
+    distance: i64,
+    state: S,
+    /// the state this one was reached from
+    previous: Option<S>,
+}
+
+impl<S> PartialEq for Queued<S> {
+    fn eq(&self, other: &Self) -> bool {
+        self.estimate == other.estimate
+    }
+}
+
+impl<S> Eq for Queued<S> {}
+
+impl<S> PartialOrd for Queued<S> {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl<S> Ord for Queued<S> {
+    fn cmp(&self, other: &Self) -> Ordering {
+        other.estimate.cmp(&self.estimate) // lowest first ordering
+    }
+}
+
+// ----------------
+
+/// All the states which can be reached from the start states, including the starts themselves
+pub fn flood_fill<S, N, I>(starts: impl IntoIterator<Item = S>, mut neighbours: N) -> HashSet<S>
+    where S: Clone + Eq + Hash, N: FnMut(&S) -> I, I: IntoIterator<Item = S>
+{
+    let mut filled = HashSet::new();
+    let mut queue = VecDeque::new();
+    for start in starts {
+        if filled.insert(start.clone()) {
+            queue.push_back(start);
+        }
+    }
+
+    while let Some(state) = queue.pop_front() {
+        for next in neighbours(&state) {
+            if filled.insert(next.clone()) {
+                queue.push_back(next);
+            }
+        }
+    }
+    filled
+}
+
+/// Label each of the states with the connected component it's in. Components are numbered from
+/// 0, in the order their first state appears. Neighbours are expected to be symmetric, and any
+/// that aren't in `states` are labelled too.
+pub fn components<S, N, I>(states: impl IntoIterator<Item = S>, mut neighbours: N) -> HashMap<S, usize>
+    where S: Clone + Eq + Hash, N: FnMut(&S) -> I, I: IntoIterator<Item = S>
+{
+    let mut labels = HashMap::new();
+    let mut count = 0;
+    for state in states {
+        if labels.contains_key(&state) { continue; }
+
+        for s in flood_fill([state], &mut neighbours) {
+            labels.insert(s, count);
+        }
+        count += 1;
+    }
+    labels
+}
+
+// ----------------
+
+/// A neighbour function for searching across the cells of a grid: the neighbours of each point,
+/// with or without diagonals, whose cells are `open`
+pub fn grid_neighbours<'a, T, G, F>(grid: &'a G, connectivity: Connectivity, open: F) -> impl FnMut(&Point) -> Vec<Point> + 'a
+    where G: Cells<T>, F: Fn(&Point, &T) -> bool + 'a
+{
+    move |p| {
+        let mut neighbours = grid.neighbours(p, connectivity);
+        neighbours.retain(|n| grid.get(n).is_some_and(|v| open(n, v)));
+        neighbours
+    }
+}
+
+// -------------------------------------------------------------------------------------------------
+// tests
+
+#[cfg(test)]
+mod tests {
+    use crate::utils::grid::Grid;
+
+    use super::*;
+
+    fn maze() -> Grid<char> {
+        "..#..\n\
+         .##.#\n\
+         ...#.\n\
+         .#...".parse().unwrap()
+    }
+
+    #[test]
+    fn bfs_distances() {
+        let maze = maze();
+        let search = bfs([Point::new(0, 0)], grid_neighbours(&maze, Connectivity::Four, |_, v| *v == '.'));
+
+        assert_eq!(Some(5), search.distance(&Point::new(2, 3)));
+        assert_eq!(Some(8), search.distance(&Point::new(4, 2)));
+        // walled off
+        assert_eq!(None, search.distance(&Point::new(3, 0)));
+        assert_eq!(Some(vec![Point::new(0, 0), Point::new(1, 0)]), search.path_to(&Point::new(1, 0)));
+    }
+
+    #[test]
+    fn weighted_search() {
+        // moving down from the left edge costs 10, so it's cheaper to go along first
+        let neighbours = |p: &Point| if p.x < 3 && p.y < 3 { vec![Point::new(p.x + 1, p.y), Point::new(p.x, p.y + 1)] } else { vec![] };
+        let cost = |a: &Point, b: &Point| if b.y > a.y && a.x == 0 { 10 } else { 1 };
+        let target = Point::new(2, 1);
+
+        let search = dijkstra([Point::new(0, 0)], neighbours, cost, |p| *p == target);
+        assert_eq!(Some(target), search.goal);
+        assert_eq!(Some(3), search.distance(&target));
+
+        let manhattan = |p: &Point| (target.x - p.x).abs() + (target.y - p.y).abs();
+        let search = a_star([Point::new(0, 0)], neighbours, cost, manhattan, |p| *p == target);
+        assert_eq!(Some(3), search.distance(&target));
+        assert_eq!(4, search.path_to(&target).unwrap().len());
+    }
+
+    #[test]
+    fn label_components() {
+        let maze = maze();
+        let filled = flood_fill([Point::new(0, 0)], grid_neighbours(&maze, Connectivity::Four, |_, v| *v == '.'));
+        assert_eq!(11, filled.len());
+
+        let labels = components(maze.find_all(|v| *v == '.'), grid_neighbours(&maze, Connectivity::Four, |_, v| *v == '.'));
+        assert_eq!(Some(&0), labels.get(&Point::new(4, 2)));
+        assert_eq!(Some(&1), labels.get(&Point::new(3, 0)));
+        assert_eq!(labels.get(&Point::new(4, 0)), labels.get(&Point::new(3, 1)));
+        assert_eq!(1, labels.values().max().copied().unwrap());
+
+        // with diagonals, (3,1) and (4,2) are joined, so there's only one
+        let labels = components(maze.find_all(|v| *v == '.'), grid_neighbours(&maze, Connectivity::Eight, |_, v| *v == '.'));
+        assert_eq!(0, labels.values().max().copied().unwrap());
+    }
+}
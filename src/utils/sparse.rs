@@ -0,0 +1,123 @@
+// -------------------------------------------------------------------------------------------------
+// Sparse grid
+
+use std::collections::HashMap;
+
+use crate::utils::grid::Cells;
+use crate::utils::point::{Bounds, Connectivity, Point};
+use crate::visualise::Frame;
+
+/// A grid which only stores the cells which have been set, so it can have any extent, including
+/// negative coordinates. Its bounds grow to fit the cells as they're set.
+#[derive(Debug, Clone)]
+pub struct SparseGrid<T> {
+    cells: HashMap<Point, T>,
+    /// area covered by the cells, if there are any
+    extent: Option<Bounds>,
+}
+
+impl<T> SparseGrid<T> {
+    pub fn new() -> SparseGrid<T> {
+        SparseGrid { cells: HashMap::new(), extent: None }
+    }
+
+    pub fn contains(&self, p: &Point) -> bool {
+        self.cells.contains_key(p)
+    }
+
+    /// Draw the area covered by the grid, using the given function to decide what to show for
+    /// each cell, whether it's been set or not
+    pub fn visualise<F, S>(&self, to_str: F) -> Frame
+        where S: AsRef<str>, F: Fn(Option<&T>, &Point) -> S
+    {
+        let bounds = self.bounds();
+        let rows = (bounds.min.y..=bounds.max.y)
+            .map(|y| (bounds.min.x..=bounds.max.x)
+                .map(|x| {
+                    let p = Point::new(x, y);
+                    to_str(self.cells.get(&p), &p).as_ref().to_owned()
+                })
+                .collect())
+            .collect();
+
+        Frame::new(rows)
+    }
+}
+
+impl<T> Default for SparseGrid<T> {
+    fn default() -> Self {
+        SparseGrid::new()
+    }
+}
+
+impl<T> Cells<T> for SparseGrid<T> {
+    fn get(&self, p: &Point) -> Option<&T> {
+        self.cells.get(p)
+    }
+
+    /// Any point can be set, and the bounds grow to include it
+    fn set(&mut self, p: &Point, value: T) {
+        self.extent = Some(match self.extent.take() {
+            None => Bounds { min: *p, max: *p },
+            Some(Bounds { min, max }) => Bounds {
+                min: Point::new(min.x.min(p.x), min.y.min(p.y)),
+                max: Point::new(max.x.max(p.x), max.y.max(p.y)),
+            },
+        });
+        self.cells.insert(*p, value);
+    }
+
+    /// Every point has all of its neighbours, since the grid has no edges
+    fn neighbours(&self, p: &Point, connectivity: Connectivity) -> Vec<Point> {
+        p.neighbours(connectivity)
+    }
+
+    /// The smallest bounds containing every cell which has been set. If there aren't any, these
+    /// are empty bounds which don't contain any points.
+    fn bounds(&self) -> Bounds {
+        self.extent.clone().unwrap_or(Bounds { min: Point::new(0, 0), max: Point::new(-1, -1) })
+    }
+}
+
+impl<T> FromIterator<(Point, T)> for SparseGrid<T> {
+    fn from_iter<I: IntoIterator<Item = (Point, T)>>(iter: I) -> Self {
+        let mut grid = SparseGrid::new();
+        for (p, value) in iter {
+            grid.set(&p, value);
+        }
+        grid
+    }
+}
+
+// -------------------------------------------------------------------------------------------------
+// tests
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn grow_bounds() {
+        let mut grid = SparseGrid::new();
+        assert_eq!(0, grid.bounds().area());
+        assert!(!grid.bounds().contains(&Point::new(0, 0)));
+
+        grid.set(&Point::new(-3, 2), 'a');
+        assert_eq!(Bounds { min: Point::new(-3, 2), max: Point::new(-3, 2) }, grid.bounds());
+
+        grid.set(&Point::new(4, -1), 'b');
+        grid.set(&Point::new(0, 0), 'c');
+        assert_eq!(Bounds { min: Point::new(-3, -1), max: Point::new(4, 2) }, grid.bounds());
+        assert_eq!(Some(&'a'), grid.get(&Point::new(-3, 2)));
+        assert_eq!(None, grid.get(&Point::new(-3, 1)));
+        assert!(grid.contains(&Point::new(0, 0)));
+    }
+
+    #[test]
+    fn visualise_sparse() {
+        let grid = [(Point::new(-1, 0), '#'), (Point::new(1, 1), '#')].into_iter().collect::<SparseGrid<_>>();
+
+        let frame = grid.visualise(|v, _| if v.is_some() { "#" } else { "." });
+        assert_eq!(vec!["#..".to_owned(), "..#".to_owned()], frame.rows);
+    }
+}
@@ -0,0 +1,259 @@
+use crate::visualise::Colour;
+
+/// Colours to fill groups of nodes with, in turn
+const GROUP_COLOURS: [Colour; 6] = [
+    Colour(255, 230, 180), Colour(200, 230, 255), Colour(210, 245, 200),
+    Colour(245, 210, 240), Colour(255, 250, 190), Colour(220, 220, 220),
+];
+const HIGHLIGHT: Colour = Colour(220, 40, 40);
+
+/// A directed graph of named nodes, to export for viewing in other tools
+#[derive(Debug, Clone, Default)]
+pub struct Graph {
+    nodes: Vec<Node>,
+    edges: Vec<Edge>,
+}
+
+#[derive(Debug, Clone)]
+struct Node {
+    name: String,
+    shape: Shape,
+    highlighted: bool,
+    /// label of the group of related nodes this is drawn with
+    group: Option<String>,
+}
+
+#[derive(Debug, Clone)]
+struct Edge {
+    from: String,
+    to: String,
+    label: Option<String>,
+    highlighted: bool,
+}
+
+/// How to draw a node
+#[derive(Debug, Clone, Copy, Eq, PartialEq)]
+pub enum Shape {
+    Box,
+    Circle,
+    DoubleCircle,
+    Diamond,
+    Ellipse,
+    Hexagon,
+}
+
+impl Shape {
+    fn dot_name(&self) -> &'static str {
+        match self {
+            Shape::Box => "box",
+            Shape::Circle => "circle",
+            Shape::DoubleCircle => "doublecircle",
+            Shape::Diamond => "diamond",
+            Shape::Ellipse => "ellipse",
+            Shape::Hexagon => "hexagon",
+        }
+    }
+
+    /// Brackets to put around a node's label in Mermaid
+    fn mermaid_brackets(&self) -> (&'static str, &'static str) {
+        match self {
+            Shape::Box => ("[", "]"),
+            Shape::Circle => ("((", "))"),
+            Shape::DoubleCircle => ("(((", ")))"),
+            Shape::Diamond => ("{", "}"),
+            Shape::Ellipse => ("([", "])"),
+            Shape::Hexagon => ("{{", "}}"),
+        }
+    }
+}
+
+impl Graph {
+    pub fn new() -> Graph {
+        Graph::default()
+    }
+
+    /// Add a node, or change the shape of one which has already been added
+    pub fn node(&mut self, name: &str, shape: Shape) {
+        match self.nodes.iter_mut().find(|n| n.name == name) {
+            Some(node) => node.shape = shape,
+            None => self.nodes.push(Node { name: name.to_owned(), shape, highlighted: false, group: None }),
+        }
+    }
+
+    /// Add an edge between two nodes. Nodes which haven't been added yet are drawn as ellipses.
+    pub fn edge(&mut self, from: &str, to: &str) {
+        self.add_edge(from, to, None);
+    }
+
+    pub fn labelled_edge(&mut self, from: &str, to: &str, label: &str) {
+        self.add_edge(from, to, Some(label.to_owned()));
+    }
+
+    fn add_edge(&mut self, from: &str, to: &str, label: Option<String>) {
+        for name in [from, to] {
+            if !self.nodes.iter().any(|n| n.name == name) { self.node(name, Shape::Ellipse); }
+        }
+        self.edges.push(Edge { from: from.to_owned(), to: to.to_owned(), label, highlighted: false });
+    }
+
+    /// Draw a node so it stands out
+    pub fn highlight(&mut self, name: &str) {
+        if let Some(node) = self.nodes.iter_mut().find(|n| n.name == name) {
+            node.highlighted = true;
+        }
+    }
+
+    /// Draw the edges between two nodes so they stand out
+    pub fn highlight_edge(&mut self, from: &str, to: &str) {
+        for edge in self.edges.iter_mut().filter(|e| e.from == from && e.to == to) {
+            edge.highlighted = true;
+        }
+    }
+
+    /// Draw a node together with the others in the group with this label
+    pub fn group(&mut self, name: &str, group: &str) {
+        if let Some(node) = self.nodes.iter_mut().find(|n| n.name == name) {
+            node.group = Some(group.to_owned());
+        }
+    }
+
+    /// Labels of the groups, in the order they were first used
+    fn groups(&self) -> Vec<&str> {
+        let mut groups = Vec::new();
+        for group in self.nodes.iter().filter_map(|n| n.group.as_deref()) {
+            if !groups.contains(&group) { groups.push(group); }
+        }
+        groups
+    }
+
+    /// Write the graph in Graphviz's DOT language
+    pub fn to_dot(&self) -> String {
+        let mut dot = vec!["digraph {".to_owned(), "    node [fontname=\"sans-serif\"];".to_owned()];
+
+        let node_line = |node: &Node, indent: &str| {
+            let mut attributes = vec![format!("shape={}", node.shape.dot_name())];
+            if node.highlighted {
+                attributes.push(format!("color=\"{}\"", HIGHLIGHT.hex()));
+                attributes.push("penwidth=2".to_owned());
+            }
+            format!("{}{} [{}];", indent, quote(&node.name), attributes.join(", "))
+        };
+
+        for (i, group) in self.groups().into_iter().enumerate() {
+            dot.push(format!("    subgraph cluster_{} {{", i));
+            dot.push(format!("        label={}; style=filled; color=\"{}\";", quote(group), GROUP_COLOURS[i % GROUP_COLOURS.len()].hex()));
+            for node in self.nodes.iter().filter(|n| n.group.as_deref() == Some(group)) {
+                dot.push(node_line(node, "        "));
+            }
+            dot.push("    }".to_owned());
+        }
+        for node in self.nodes.iter().filter(|n| n.group.is_none()) {
+            dot.push(node_line(node, "    "));
+        }
+
+        for edge in &self.edges {
+            let mut attributes = Vec::new();
+            if let Some(label) = &edge.label {
+                attributes.push(format!("label={}", quote(label)));
+            }
+            if edge.highlighted {
+                attributes.push(format!("color=\"{}\"", HIGHLIGHT.hex()));
+                attributes.push("penwidth=2".to_owned());
+            }
+            match attributes.is_empty() {
+                true => dot.push(format!("    {} -> {};", quote(&edge.from), quote(&edge.to))),
+                false => dot.push(format!("    {} -> {} [{}];", quote(&edge.from), quote(&edge.to), attributes.join(", "))),
+            }
+        }
+
+        dot.push("}".to_owned());
+        dot.join("\n") + "\n"
+    }
+
+    /// Write the graph as a Mermaid flowchart
+    pub fn to_mermaid(&self) -> String {
+        let mut mermaid = vec!["flowchart LR".to_owned()];
+
+        // node names can be anything, so they're given simple ids
+        let id = |name: &str| format!("n{}", self.nodes.iter().position(|n| n.name == name).unwrap_or(0));
+        let node_line = |node: &Node, indent: &str| {
+            let (open, close) = node.shape.mermaid_brackets();
+            format!("{}{}{}\"{}\"{}", indent, id(&node.name), open, mermaid_escape(&node.name), close)
+        };
+
+        for (i, group) in self.groups().into_iter().enumerate() {
+            mermaid.push(format!("    subgraph g{} [\"{}\"]", i, mermaid_escape(group)));
+            for node in self.nodes.iter().filter(|n| n.group.as_deref() == Some(group)) {
+                mermaid.push(node_line(node, "        "));
+            }
+            mermaid.push("    end".to_owned());
+            mermaid.push(format!("    style g{} fill:{}", i, GROUP_COLOURS[i % GROUP_COLOURS.len()].hex()));
+        }
+        for node in self.nodes.iter().filter(|n| n.group.is_none()) {
+            mermaid.push(node_line(node, "    "));
+        }
+
+        for edge in &self.edges {
+            match &edge.label {
+                Some(label) => mermaid.push(format!("    {} -->|\"{}\"| {}", id(&edge.from), mermaid_escape(label), id(&edge.to))),
+                None => mermaid.push(format!("    {} --> {}", id(&edge.from), id(&edge.to))),
+            }
+        }
+
+        // highlighting is applied to nodes by id, and to edges by the order they were added
+        for node in self.nodes.iter().filter(|n| n.highlighted) {
+            mermaid.push(format!("    style {} stroke:{},stroke-width:2px", id(&node.name), HIGHLIGHT.hex()));
+        }
+        for (i, _) in self.edges.iter().enumerate().filter(|(_, e)| e.highlighted) {
+            mermaid.push(format!("    linkStyle {} stroke:{},stroke-width:2px", i, HIGHLIGHT.hex()));
+        }
+
+        mermaid.join("\n") + "\n"
+    }
+}
+
+fn quote(text: &str) -> String {
+    format!("\"{}\"", text.replace('\\', "\\\\").replace('"', "\\\""))
+}
+
+/// Mermaid labels are quoted, and can contain HTML, so some characters need replacing with entities
+fn mermaid_escape(text: &str) -> String {
+    text.replace('"', "#quot;").replace('<', "#lt;").replace('>', "#gt;")
+}
+
+// -------------------------------------------------------------------------------------------------
+// tests
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn write_dot() {
+        let mut graph = Graph::new();
+        graph.node("a", Shape::Box);
+        graph.edge("a", "b");
+        graph.edge("b", "a");
+        graph.highlight("b");
+        graph.group("a", "loop");
+
+        assert_eq!("digraph {\n    node [fontname=\"sans-serif\"];\n    subgraph cluster_0 {\n        \
+                    label=\"loop\"; style=filled; color=\"#ffe6b4\";\n        \"a\" [shape=box];\n    }\n    \
+                    \"b\" [shape=ellipse, color=\"#dc2828\", penwidth=2];\n    \"a\" -> \"b\";\n    \"b\" -> \"a\";\n}\n",
+                   graph.to_dot());
+    }
+
+    #[test]
+    fn write_mermaid() {
+        let mut graph = Graph::new();
+        graph.node("in", Shape::Box);
+        graph.labelled_edge("in", "A", "x<10");
+        graph.edge("in", "R");
+        graph.highlight("in");
+        graph.highlight_edge("in", "A");
+
+        assert_eq!("flowchart LR\n    n0[\"in\"]\n    n1([\"A\"])\n    n2([\"R\"])\n    n0 -->|\"x#lt;10\"| n1\n    \
+                    n0 --> n2\n    style n0 stroke:#dc2828,stroke-width:2px\n    linkStyle 0 stroke:#dc2828,stroke-width:2px\n",
+                   graph.to_mermaid());
+    }
+}
@@ -0,0 +1,294 @@
+use std::borrow::Cow;
+use std::fs::File;
+use std::io::{self, BufWriter, Write};
+use std::path::Path;
+
+use crate::visualise::{Colour, Frame, Settings, Style, Theme, Visualiser};
+
+/// Size of the square of pixels each character is drawn as
+const CELL_SIZE: usize = 4;
+
+/// Colour for characters which aren't in a frame's palette, and for empty cells
+const FOREGROUND: Colour = Colour(200, 200, 200);
+const BACKGROUND: Colour = Colour(20, 20, 20);
+/// Colour for bold characters without one of their own
+const BOLD: Colour = Colour(255, 255, 255);
+
+/// Kinds of image which can be written
+#[derive(Debug, Clone, Copy, Eq, PartialEq)]
+pub enum ImageFormat {
+    /// a still picture of the last frame
+    Png,
+    /// an animation of all the frames
+    Gif,
+    /// vector outlines of the last drawing
+    Svg,
+}
+
+impl ImageFormat {
+    /// The format to write to a file, based on its extension
+    pub fn of(path: &str) -> Option<ImageFormat> {
+        let extension = Path::new(path).extension()?.to_str()?.to_lowercase();
+        match extension.as_str() {
+            "png" => Some(ImageFormat::Png),
+            "gif" => Some(ImageFormat::Gif),
+            "svg" => Some(ImageFormat::Svg),
+            _ => None,
+        }
+    }
+}
+
+/// Draws frames as images, with each character as a block of colour from its style. Events are
+/// ignored. Nothing is written until finished, since a GIF needs all the frames.
+pub struct ImageVisualiser {
+    file: File,
+    format: ImageFormat,
+    theme: Theme,
+    /// delay between frames of an animation, in hundredths of a second
+    delay: u16,
+    frames: Vec<Frame>,
+}
+
+impl ImageVisualiser {
+    pub fn create(path: &str, settings: &Settings) -> io::Result<ImageVisualiser> {
+        let format = ImageFormat::of(path)
+            .filter(|f| *f != ImageFormat::Svg)
+            .ok_or_else(|| io::Error::new(io::ErrorKind::InvalidInput, format!("Can't write an image to {}, it must be a .png or .gif", path)))?;
+
+        Ok(ImageVisualiser {
+            file: File::create(path)?,
+            format,
+            theme: settings.theme,
+            delay: (100 / settings.fps.max(1)).max(1) as u16,
+            frames: Vec::new(),
+        })
+    }
+}
+
+impl Visualiser for ImageVisualiser {
+    fn frame(&mut self, frame: &Frame) {
+        match self.format {
+            ImageFormat::Gif => self.frames.push(frame.clone()),
+            // only the last frame is kept for a still picture
+            _ => self.frames = vec![frame.clone()],
+        }
+    }
+
+    fn event(&mut self, _event: &str) {}
+
+    fn finish(&mut self) -> io::Result<()> {
+        if self.frames.is_empty() {
+            return Err(io::Error::other("Nothing to draw, no frames were shown"));
+        }
+
+        // every frame is drawn on a canvas big enough for the largest of them
+        let columns = self.frames.iter()
+            .flat_map(|f| f.rows.iter().map(|r| r.chars().count()))
+            .max().unwrap_or(0);
+        let rows = self.frames.iter().map(|f| f.rows.len()).max().unwrap_or(0);
+        let rasters = self.frames.iter()
+            .map(|f| Raster::of(f, columns, rows, self.theme))
+            .collect::<Vec<_>>();
+
+        let out = BufWriter::new(&self.file);
+        match self.format {
+            ImageFormat::Gif => write_gif(out, &rasters, self.delay),
+            _ => write_png(out, &rasters[rasters.len() - 1]),
+        }
+    }
+}
+
+/// Draw a single frame as a PNG
+pub fn frame_png(frame: &Frame, theme: Theme) -> io::Result<Vec<u8>> {
+    let columns = frame.rows.iter().map(|r| r.chars().count()).max().unwrap_or(0);
+    let mut png = Vec::new();
+    write_png(&mut png, &Raster::of(frame, columns, frame.rows.len(), theme))?;
+    Ok(png)
+}
+
+// -------------------------------------------------------------------------------------------------
+// rasterising
+
+/// A frame drawn as pixels
+#[derive(Debug)]
+struct Raster {
+    width: usize,
+    height: usize,
+    /// colour of each pixel, row by row
+    pixels: Vec<Colour>,
+}
+
+impl Raster {
+    /// Draw a frame, with each character as a square of its colour, padded with the background
+    /// to fill the given number of columns and rows. Characters with a background colour are
+    /// drawn as a smaller square of their colour on top of it.
+    fn of(frame: &Frame, columns: usize, rows: usize, theme: Theme) -> Raster {
+        let width = columns * CELL_SIZE;
+        let height = rows * CELL_SIZE;
+        let mut pixels = vec![BACKGROUND; width * height];
+
+        let mut fill = |x: usize, y: usize, inset: usize, colour: Colour| {
+            for py in y * CELL_SIZE + inset..(y + 1) * CELL_SIZE - inset {
+                pixels[py * width + x * CELL_SIZE + inset..py * width + (x + 1) * CELL_SIZE - inset].fill(colour);
+            }
+        };
+        for y in 0..frame.rows.len() {
+            for (x, (c, style)) in frame.cells(y).enumerate() {
+                let style = theme.apply(style);
+                match (style.bg, colour_of(c, &style)) {
+                    (Some(bg), Some(fg)) => {
+                        fill(x, y, 0, bg);
+                        fill(x, y, CELL_SIZE / 4, fg);
+                    }
+                    (Some(colour), None) | (None, Some(colour)) => fill(x, y, 0, colour),
+                    (None, None) => {}
+                }
+            }
+        }
+
+        Raster { width, height, pixels }
+    }
+}
+
+/// The colour to draw a character in, if any. Those without a colour are shown in the foreground
+/// colour, except for spaces and dots, which are usually used for empty cells.
+fn colour_of(c: char, style: &Style) -> Option<Colour> {
+    match style.fg {
+        Some(colour) => Some(colour),
+        None if c == ' ' || c == '.' => None,
+        None if style.bold => Some(BOLD),
+        None => Some(FOREGROUND),
+    }
+}
+
+// -------------------------------------------------------------------------------------------------
+// encoding
+
+fn write_png<W: Write>(out: W, raster: &Raster) -> io::Result<()> {
+    let mut encoder = png::Encoder::new(out, raster.width as u32, raster.height as u32);
+    encoder.set_color(png::ColorType::Rgb);
+    encoder.set_depth(png::BitDepth::Eight);
+
+    let data = raster.pixels.iter()
+        .flat_map(|c| [c.0, c.1, c.2])
+        .collect::<Vec<_>>();
+    let mut writer = encoder.write_header().map_err(io::Error::other)?;
+    writer.write_image_data(&data).map_err(io::Error::other)?;
+    writer.finish().map_err(io::Error::other)
+}
+
+/// Write the rasters as a looping animation. GIFs are limited to 256 colours, which is plenty
+/// given each one comes from a palette of a few characters.
+fn write_gif<W: Write>(out: W, rasters: &[Raster], delay: u16) -> io::Result<()> {
+    let (width, height) = (rasters[0].width, rasters[0].height);
+    let (Ok(width), Ok(height)) = (u16::try_from(width), u16::try_from(height)) else {
+        return Err(io::Error::other(format!("Frames are too large for a GIF ({} x {} pixels)", width, height)));
+    };
+
+    // use one table of colours for every frame
+    let mut colours = Vec::new();
+    let indexed = rasters.iter()
+        .map(|raster| raster.pixels.iter()
+            .map(|colour| {
+                let index = colours.iter().position(|c| c == colour).unwrap_or_else(|| {
+                    colours.push(*colour);
+                    colours.len() - 1
+                });
+                u8::try_from(index).map_err(|_| io::Error::other("Too many colours for a GIF"))
+            })
+            .collect::<io::Result<Vec<_>>>())
+        .collect::<io::Result<Vec<_>>>()?;
+    let table = colours.iter().flat_map(|c| [c.0, c.1, c.2]).collect::<Vec<_>>();
+
+    let mut encoder = gif::Encoder::new(out, width, height, &table).map_err(io::Error::other)?;
+    encoder.set_repeat(gif::Repeat::Infinite).map_err(io::Error::other)?;
+    for pixels in indexed {
+        let frame = gif::Frame {
+            width,
+            height,
+            delay,
+            buffer: Cow::Owned(pixels),
+            ..gif::Frame::default()
+        };
+        encoder.write_frame(&frame).map_err(io::Error::other)?;
+    }
+    Ok(())
+}
+
+// -------------------------------------------------------------------------------------------------
+// tests
+
+#[cfg(test)]
+mod tests {
+    use crate::visualise::Palette;
+
+    use super::*;
+
+    #[test]
+    fn format_from_extension() {
+        assert_eq!(Some(ImageFormat::Png), ImageFormat::of("day10.png"));
+        assert_eq!(Some(ImageFormat::Gif), ImageFormat::of("out/day14.GIF"));
+        assert_eq!(Some(ImageFormat::Svg), ImageFormat::of("day18.svg"));
+        assert_eq!(None, ImageFormat::of("day14.txt"));
+        assert_eq!(None, ImageFormat::of("gif"));
+    }
+
+    #[test]
+    fn rasterise_frame() {
+        const PALETTE: &Palette = &[('#', Colour(255, 0, 0))];
+        let frame = Frame::new(vec!["#.".to_owned(), "O".to_owned()]).with_palette(PALETTE);
+
+        let raster = Raster::of(&frame, 3, 2, Theme::Default);
+        assert_eq!((3 * CELL_SIZE, 2 * CELL_SIZE), (raster.width, raster.height));
+
+        let pixel = |x: usize, y: usize| raster.pixels[y * CELL_SIZE * raster.width + x * CELL_SIZE];
+        assert_eq!(Colour(255, 0, 0), pixel(0, 0));
+        assert_eq!(BACKGROUND, pixel(1, 0));
+        assert_eq!(BACKGROUND, pixel(2, 0));
+        assert_eq!(FOREGROUND, pixel(0, 1));
+        assert_eq!(BACKGROUND, pixel(1, 1));
+    }
+
+    #[test]
+    fn rasterise_styles() {
+        let frame = Frame::styled(vec!["#.x".to_owned()], vec![vec![
+            Style::new().fg(Colour(255, 0, 0)).bg(Colour(0, 0, 255)),
+            Style::new().bg(Colour(0, 255, 0)),
+            Style::new().bold(),
+        ]]);
+
+        let raster = Raster::of(&frame, 3, 1, Theme::Default);
+        let pixel = |x: usize, y: usize| raster.pixels[y * raster.width + x];
+        assert_eq!(Colour(0, 0, 255), pixel(0, 0));
+        assert_eq!(Colour(255, 0, 0), pixel(CELL_SIZE / 2, CELL_SIZE / 2));
+        assert_eq!(Colour(0, 255, 0), pixel(CELL_SIZE + CELL_SIZE / 2, CELL_SIZE / 2));
+        assert_eq!(BOLD, pixel(2 * CELL_SIZE, 0));
+
+        let raster = Raster::of(&frame, 3, 1, Theme::Monochrome);
+        assert_eq!(Colour(76, 76, 76), raster.pixels[CELL_SIZE / 2 * raster.width + CELL_SIZE / 2]);
+    }
+
+    #[test]
+    fn encode_gif() {
+        const PALETTE: &Palette = &[('#', Colour(255, 0, 0))];
+        let frames = [Frame::new(vec!["#.".to_owned()]), Frame::new(vec![".#".to_owned()])];
+        let rasters = frames.map(|f| Raster::of(&f.with_palette(PALETTE), 2, 1, Theme::Default));
+
+        let mut gif = Vec::new();
+        write_gif(&mut gif, &rasters, 10).unwrap();
+
+        let mut options = gif::DecodeOptions::new();
+        options.set_color_output(gif::ColorOutput::RGBA);
+        let mut decoder = options.read_info(gif.as_slice()).unwrap();
+        assert_eq!((2 * CELL_SIZE as u16, CELL_SIZE as u16), (decoder.width(), decoder.height()));
+
+        // each frame has the red cell on a different side
+        let mut red = Vec::new();
+        while let Some(frame) = decoder.read_next_frame().unwrap() {
+            assert_eq!(10, frame.delay);
+            let pixel = |x: usize| &frame.buffer[x * CELL_SIZE * 4..x * CELL_SIZE * 4 + 3];
+            red.push([pixel(0) == [255, 0, 0], pixel(1) == [255, 0, 0]]);
+        }
+        assert_eq!(vec![[true, false], [false, true]], red);
+    }
+}
@@ -0,0 +1,308 @@
+use std::borrow::Cow;
+use std::fmt::{Display, Formatter};
+use std::fs::File;
+use std::io::{self, BufWriter, Write};
+use std::str::FromStr;
+
+pub use graph::{Graph, Shape};
+pub use image::{frame_png, ImageFormat, ImageVisualiser};
+pub use style::{Style, Theme};
+pub use svg::{Drawing, SvgVisualiser};
+pub use terminal::{AnimatedVisualiser, TerminalVisualiser};
+pub use trace::{replay, TraceVisualiser};
+
+mod graph;
+mod image;
+mod style;
+mod svg;
+mod terminal;
+mod trace;
+
+/// Picture of the state of a solution at some point, as rows of text (eg. a rendered grid)
+#[derive(Debug, Clone, Default, Eq, PartialEq)]
+pub struct Frame {
+    pub rows: Vec<String>,
+    /// colours to show the characters in, where that's supported
+    pub palette: Cow<'static, Palette>,
+    /// style of each character, row by row, which takes the place of the palette. Empty unless
+    /// the frame was drawn with styles.
+    pub styles: Vec<Vec<Style>>,
+}
+
+impl Frame {
+    pub fn new(rows: Vec<String>) -> Frame {
+        Frame { rows, palette: Cow::Borrowed(&[]), styles: Vec::new() }
+    }
+
+    /// A frame with a style for each character of each row
+    pub fn styled(rows: Vec<String>, styles: Vec<Vec<Style>>) -> Frame {
+        Frame { styles, ..Frame::new(rows) }
+    }
+
+    pub fn with_palette(self, palette: &'static Palette) -> Frame {
+        Frame { palette: Cow::Borrowed(palette), ..self }
+    }
+
+    /// The characters of a row, each with the style to show it in. That's its own style if the
+    /// frame has them, otherwise its colour from the palette.
+    pub fn cells(&self, row: usize) -> impl Iterator<Item = (char, Style)> + '_ {
+        let styles = self.styles.get(row);
+        self.rows[row].chars().enumerate().map(move |(i, c)| {
+            let style = match styles {
+                Some(styles) => styles.get(i).copied().unwrap_or_default(),
+                None => self.palette.iter()
+                    .find(|(p, _)| *p == c)
+                    .map(|(_, colour)| Style::new().fg(*colour))
+                    .unwrap_or_default(),
+            };
+            (c, style)
+        })
+    }
+}
+
+/// Red, green and blue components of a colour
+#[derive(Debug, Clone, Copy, Eq, PartialEq)]
+pub struct Colour(pub u8, pub u8, pub u8);
+
+impl Colour {
+    /// The colour as used in HTML, eg. `#ff8000`
+    pub fn hex(&self) -> String {
+        format!("#{:02x}{:02x}{:02x}", self.0, self.1, self.2)
+    }
+}
+
+/// The colour to show each character in. Characters which aren't listed are shown in the
+/// default colour.
+pub type Palette = [(char, Colour)];
+
+impl Display for Frame {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        for row in &self.rows {
+            writeln!(f, "{}", row)?;
+        }
+        Ok(())
+    }
+}
+
+/// Destination for the visual output of a solution. Solutions emit frames showing their state,
+/// and events describing what they're doing, and the visualiser decides what to do with them.
+pub trait Visualiser {
+    /// Whether anything is being shown. Solutions should check this before going to the effort of
+    /// preparing frames or events.
+    fn enabled(&self) -> bool {
+        true
+    }
+
+    /// Show the current state
+    fn frame(&mut self, frame: &Frame);
+
+    /// Describe something which has happened
+    fn event(&mut self, event: &str);
+
+    /// Show shapes which are too large to draw cell by cell. Ignored by visualisers which can
+    /// only show text.
+    fn drawing(&mut self, _drawing: &Drawing) {}
+
+    /// Called once the solution has finished, to report any problem showing its output
+    fn finish(&mut self) -> io::Result<()> {
+        Ok(())
+    }
+}
+
+// -------------------------------------------------------------------------------------------------
+// implementations
+
+/// Discards everything, for when visualisation isn't wanted
+pub struct NullVisualiser;
+
+impl Visualiser for NullVisualiser {
+    fn enabled(&self) -> bool {
+        false
+    }
+
+    fn frame(&mut self, _frame: &Frame) {}
+
+    fn event(&mut self, _event: &str) {}
+}
+
+/// Writes everything to a file, in the same format as it would be shown in the terminal
+pub struct FileVisualiser {
+    out: BufWriter<File>,
+    /// first error writing to the file, which is reported when finished
+    error: Option<io::Error>,
+}
+
+impl FileVisualiser {
+    pub fn create(path: &str) -> io::Result<FileVisualiser> {
+        Ok(FileVisualiser { out: BufWriter::new(File::create(path)?), error: None })
+    }
+
+    fn write(&mut self, text: &dyn Display) {
+        if self.error.is_none() {
+            self.error = writeln!(self.out, "{}", text).err();
+        }
+    }
+}
+
+impl Visualiser for FileVisualiser {
+    fn frame(&mut self, frame: &Frame) {
+        self.write(frame);
+    }
+
+    fn event(&mut self, event: &str) {
+        self.write(&event);
+    }
+
+    fn finish(&mut self) -> io::Result<()> {
+        match self.error.take() {
+            Some(e) => Err(e),
+            None => self.out.flush(),
+        }
+    }
+}
+
+/// Keeps the last frame and drawing shown, so they can be included in a report
+#[derive(Default)]
+pub struct Capture {
+    frame: Option<Frame>,
+    drawing: Option<Drawing>,
+}
+
+impl Capture {
+    /// The last frame and drawing shown
+    pub fn take(self) -> (Option<Frame>, Option<Drawing>) {
+        (self.frame, self.drawing)
+    }
+}
+
+impl Visualiser for Capture {
+    fn frame(&mut self, frame: &Frame) {
+        self.frame = Some(frame.clone());
+    }
+
+    fn event(&mut self, _event: &str) {}
+
+    fn drawing(&mut self, drawing: &Drawing) {
+        self.drawing = Some(drawing.clone());
+    }
+}
+
+/// Keeps everything, so it can be inspected by tests
+#[cfg(test)]
+#[derive(Default)]
+pub struct Recorder {
+    pub frames: Vec<Frame>,
+    pub events: Vec<String>,
+    pub drawings: Vec<Drawing>,
+}
+
+#[cfg(test)]
+impl Visualiser for Recorder {
+    fn frame(&mut self, frame: &Frame) {
+        self.frames.push(frame.clone());
+    }
+
+    fn event(&mut self, event: &str) {
+        self.events.push(event.to_owned());
+    }
+
+    fn drawing(&mut self, drawing: &Drawing) {
+        self.drawings.push(drawing.clone());
+    }
+}
+
+// -------------------------------------------------------------------------------------------------
+// selection
+
+/// Where visual output should go
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub enum Target {
+    None,
+    Terminal,
+    /// redraw frames in place in the terminal
+    Animate,
+    File(String),
+    /// draw the frames into a PNG or GIF, or the drawings into an SVG
+    Image(String),
+    /// record the frames and events, to replay later
+    Trace(String),
+}
+
+/// Options for the visualisers which use them
+#[derive(Debug, Clone)]
+pub struct Settings {
+    /// frames per second when animating, in the terminal or a GIF
+    pub fps: u32,
+    /// adjustments to the styles of frames, where they're shown in colour
+    pub theme: Theme,
+}
+
+impl Target {
+    /// Create a visualiser which sends its output to this target
+    pub fn create(&self, settings: &Settings) -> io::Result<Box<dyn Visualiser>> {
+        Ok(match self {
+            Target::None => Box::new(NullVisualiser),
+            Target::Terminal => Box::new(TerminalVisualiser::new(settings.theme)),
+            Target::Animate => Box::new(AnimatedVisualiser::new(settings)),
+            Target::File(path) => Box::new(FileVisualiser::create(path)?),
+            Target::Image(path) if ImageFormat::of(path) == Some(ImageFormat::Svg) => Box::new(SvgVisualiser::create(path)?),
+            Target::Image(path) => Box::new(ImageVisualiser::create(path, settings)?),
+            Target::Trace(path) => Box::new(TraceVisualiser::create(path)?),
+        })
+    }
+}
+
+/// Targets are given by name, or else are taken to be the file to write to
+impl FromStr for Target {
+    type Err = String;
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        match value {
+            "" => Err("No visualisation target given".to_owned()),
+            "none" => Ok(Target::None),
+            "terminal" => Ok(Target::Terminal),
+            "animate" => Ok(Target::Animate),
+            // anything else must look like a path, so a mistyped name isn't taken as a file
+            path if path.contains(['.', '/']) => Ok(Target::File(path.to_owned())),
+            name => Err(format!("Unknown visualisation target '{}', expected terminal, animate, none or a file path", name)),
+        }
+    }
+}
+
+// -------------------------------------------------------------------------------------------------
+// tests
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_target() {
+        assert_eq!(Ok(Target::Terminal), "terminal".parse());
+        assert_eq!(Ok(Target::None), "none".parse());
+        assert_eq!(Ok(Target::Animate), "animate".parse());
+        assert_eq!(Ok(Target::File("out.txt".to_owned())), "out.txt".parse());
+        assert_eq!(Ok(Target::File("out/trace".to_owned())), "out/trace".parse());
+        assert!("".parse::<Target>().is_err());
+        assert_eq!(Err("Unknown visualisation target 'animte', expected terminal, animate, none or a file path".to_owned()), "animte".parse::<Target>());
+    }
+
+    #[test]
+    fn display_frame() {
+        let frame = Frame::new(vec!["#.".to_owned(), ".#".to_owned()]);
+        assert_eq!("#.\n.#\n", frame.to_string());
+    }
+
+    #[test]
+    fn cell_styles() {
+        const PALETTE: &Palette = &[('#', Colour(255, 0, 0))];
+        let red = Style::new().fg(Colour(255, 0, 0));
+
+        let frame = Frame::new(vec!["#.".to_owned()]).with_palette(PALETTE);
+        assert_eq!(vec![('#', red), ('.', Style::new())], frame.cells(0).collect::<Vec<_>>());
+
+        // styles take the place of the palette
+        let frame = Frame::styled(vec!["#.".to_owned()], vec![vec![Style::new(), red.bold()]]).with_palette(PALETTE);
+        assert_eq!(vec![('#', Style::new()), ('.', red.bold())], frame.cells(0).collect::<Vec<_>>());
+    }
+}
@@ -0,0 +1,156 @@
+use std::str::FromStr;
+
+use crate::visualise::Colour;
+
+/// How to show a character: its colour, the colour behind it, and whether it's bold. Anything
+/// which isn't set is left as the visualiser's default.
+#[derive(Debug, Clone, Copy, Default, Eq, PartialEq)]
+pub struct Style {
+    pub fg: Option<Colour>,
+    pub bg: Option<Colour>,
+    pub bold: bool,
+}
+
+impl Style {
+    pub fn new() -> Style {
+        Style::default()
+    }
+
+    pub fn fg(self, colour: Colour) -> Style {
+        Style { fg: Some(colour), ..self }
+    }
+
+    pub fn bg(self, colour: Colour) -> Style {
+        Style { bg: Some(colour), ..self }
+    }
+
+    pub fn bold(self) -> Style {
+        Style { bold: true, ..self }
+    }
+
+    /// Whether this is the default style, with nothing set
+    pub fn is_plain(&self) -> bool {
+        *self == Style::default()
+    }
+}
+
+/// Colours bright enough to stand out against a dark background, and from each other
+const HIGH_CONTRAST: &[Colour] = &[
+    Colour(255, 255, 0), Colour(0, 255, 255), Colour(255, 0, 255),
+    Colour(0, 255, 0), Colour(255, 0, 0), Colour(80, 80, 255),
+];
+
+/// The Okabe-Ito palette, which can be told apart with the common kinds of colour blindness,
+/// plus white and grey
+const COLOUR_BLIND: &[Colour] = &[
+    Colour(230, 159, 0), Colour(86, 180, 233), Colour(0, 158, 115), Colour(240, 228, 66),
+    Colour(0, 114, 178), Colour(213, 94, 0), Colour(204, 121, 167),
+    Colour(255, 255, 255), Colour(128, 128, 128),
+];
+
+/// Named ways of adjusting the styles of a frame, to make them easier to read
+#[derive(Debug, Clone, Copy, Default, Eq, PartialEq)]
+pub enum Theme {
+    /// the colours chosen by each solution
+    #[default]
+    Default,
+    /// the nearest of a few bright colours, with coloured characters in bold
+    HighContrast,
+    /// the nearest of a set of colours which can be told apart with colour blindness
+    ColourBlind,
+    /// shades of grey, with coloured characters in bold
+    Monochrome,
+}
+
+impl Theme {
+    pub const NAMES: &'static [&'static str] = &["default", "high-contrast", "colour-blind", "monochrome"];
+
+    /// Adjust a style for this theme
+    pub fn apply(&self, style: Style) -> Style {
+        match self {
+            Theme::Default => style,
+            Theme::HighContrast => Style {
+                fg: style.fg.map(vivid),
+                bg: style.bg.map(vivid),
+                bold: style.bold || style.fg.is_some(),
+            },
+            Theme::ColourBlind => Style {
+                fg: style.fg.map(|c| nearest(c, COLOUR_BLIND)),
+                bg: style.bg.map(|c| nearest(c, COLOUR_BLIND)),
+                bold: style.bold,
+            },
+            Theme::Monochrome => Style {
+                fg: style.fg.map(grey),
+                bg: style.bg.map(grey),
+                bold: style.bold || style.fg.is_some(),
+            },
+        }
+    }
+}
+
+impl FromStr for Theme {
+    type Err = String;
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        match value {
+            "default" => Ok(Theme::Default),
+            "high-contrast" => Ok(Theme::HighContrast),
+            "colour-blind" | "color-blind" => Ok(Theme::ColourBlind),
+            "monochrome" => Ok(Theme::Monochrome),
+            _ => Err(format!("Unknown theme '{}', expected one of: {}", value, Theme::NAMES.join(", "))),
+        }
+    }
+}
+
+/// The colour from the choices which looks most like the given one
+fn nearest(colour: Colour, choices: &[Colour]) -> Colour {
+    let distance = |c: &Colour| {
+        let d = |a: u8, b: u8| (a as i32 - b as i32).pow(2);
+        d(colour.0, c.0) + d(colour.1, c.1) + d(colour.2, c.2)
+    };
+    choices.iter().copied().min_by_key(distance).unwrap_or(colour)
+}
+
+/// The nearest high contrast colour. Greys are made either white or mid grey, so that they stay
+/// grey rather than picking up a colour.
+fn vivid(colour: Colour) -> Colour {
+    let Colour(r, g, b) = colour;
+    match r.max(g).max(b) - r.min(g).min(b) {
+        spread if spread >= 40 => nearest(colour, HIGH_CONTRAST),
+        _ if grey(colour).0 >= 160 => Colour(255, 255, 255),
+        _ => Colour(128, 128, 128),
+    }
+}
+
+/// A grey as bright as the colour
+fn grey(colour: Colour) -> Colour {
+    let luma = (299 * colour.0 as u32 + 587 * colour.1 as u32 + 114 * colour.2 as u32) / 1000;
+    Colour(luma as u8, luma as u8, luma as u8)
+}
+
+// -------------------------------------------------------------------------------------------------
+// tests
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn apply_theme() {
+        let style = Style::new().fg(Colour(90, 160, 255)).bg(Colour(80, 200, 80));
+
+        assert_eq!(style, Theme::Default.apply(style));
+        assert_eq!(Style::new().fg(Colour(80, 80, 255)).bg(Colour(0, 255, 0)).bold(), Theme::HighContrast.apply(style));
+        assert_eq!(Style::new().fg(Colour(86, 180, 233)).bg(Colour(0, 158, 115)), Theme::ColourBlind.apply(style));
+        assert_eq!(Style::new().fg(Colour(149, 149, 149)).bg(Colour(150, 150, 150)).bold(), Theme::Monochrome.apply(style));
+        assert_eq!(Style::new(), Theme::HighContrast.apply(Style::new()));
+        assert_eq!(Some(Colour(128, 128, 128)), Theme::HighContrast.apply(Style::new().fg(Colour(110, 110, 110))).fg);
+    }
+
+    #[test]
+    fn parse_theme() {
+        assert_eq!(Ok(Theme::HighContrast), "high-contrast".parse());
+        assert_eq!(Ok(Theme::ColourBlind), "color-blind".parse());
+        assert!("sepia".parse::<Theme>().is_err());
+    }
+}
@@ -0,0 +1,184 @@
+use std::fs::File;
+use std::io::{self, Write};
+
+use crate::utils::path::Path;
+use crate::utils::point::{Bounds, Point};
+use crate::visualise::{Colour, Frame, Visualiser};
+
+/// Length of the longest side of a drawing, in pixels
+const SIZE: f64 = 800.0;
+/// Space left around the edge of a drawing, in pixels
+const MARGIN: f64 = 10.0;
+
+const BACKGROUND: Colour = Colour(20, 20, 20);
+
+/// Shapes to be drawn as vector graphics, so large areas can be shown without drawing every cell
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct Drawing {
+    pub shapes: Vec<Shape>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum Shape {
+    /// closed polygon through the points
+    Outline(Vec<Point>, Colour),
+    Rectangle(Bounds, Colour),
+    /// separate points, each shown as a dot
+    Points(Vec<Point>, Colour),
+}
+
+impl Drawing {
+    pub fn new() -> Drawing {
+        Drawing::default()
+    }
+
+    /// Draw the outline of the polygon with a corner at each point of the path
+    pub fn path(mut self, path: &Path, colour: Colour) -> Drawing {
+        self.shapes.push(Shape::Outline(path.points().to_vec(), colour));
+        self
+    }
+
+    pub fn bounds(mut self, bounds: &Bounds, colour: Colour) -> Drawing {
+        self.shapes.push(Shape::Rectangle(bounds.clone(), colour));
+        self
+    }
+
+    pub fn points(mut self, points: &[Point], colour: Colour) -> Drawing {
+        self.shapes.push(Shape::Points(points.to_vec(), colour));
+        self
+    }
+
+    /// The area covered by all the shapes
+    fn extent(&self) -> Option<Bounds> {
+        self.shapes.iter()
+            .flat_map(|shape| match shape {
+                Shape::Outline(points, _) | Shape::Points(points, _) => points.clone(),
+                Shape::Rectangle(bounds, _) => vec![bounds.min, bounds.max],
+            })
+            .fold(None, |extent, p| match extent {
+                None => Some(Bounds { min: p, max: p }),
+                Some(Bounds { min, max }) => Some(Bounds {
+                    min: Point::new(min.x.min(p.x), min.y.min(p.y)),
+                    max: Point::new(max.x.max(p.x), max.y.max(p.y)),
+                }),
+            })
+    }
+
+    /// Write the drawing as an SVG document. It's scaled so that the longest side is `size`
+    /// pixels, however far apart the points are.
+    pub fn to_svg(&self, size: f64) -> String {
+        let extent = self.extent().unwrap_or(Bounds { min: Point::new(0, 0), max: Point::new(0, 0) });
+        let span = (extent.max.x - extent.min.x).max(extent.max.y - extent.min.y).max(1);
+        let scale = Scale { origin: extent.min, factor: size / span as f64 };
+
+        let width = (extent.max.x - extent.min.x) as f64 * scale.factor + 2.0 * MARGIN;
+        let height = (extent.max.y - extent.min.y) as f64 * scale.factor + 2.0 * MARGIN;
+        let mut svg = vec![
+            format!("<svg xmlns=\"http://www.w3.org/2000/svg\" width=\"{:.0}\" height=\"{:.0}\" viewBox=\"0 0 {:.1} {:.1}\">",
+                    width.ceil(), height.ceil(), width, height),
+            format!("<rect width=\"100%\" height=\"100%\" fill=\"{}\"/>", BACKGROUND.hex()),
+        ];
+
+        for shape in &self.shapes {
+            svg.push(match shape {
+                Shape::Outline(points, colour) => {
+                    let points = points.iter()
+                        .map(|p| scale.apply(p))
+                        .map(|(x, y)| format!("{:.1},{:.1}", x, y))
+                        .collect::<Vec<_>>();
+                    format!("<polygon points=\"{}\" fill=\"{}\" fill-opacity=\"0.3\" stroke=\"{}\" stroke-width=\"1.5\"/>",
+                            points.join(" "), colour.hex(), colour.hex())
+                }
+                Shape::Rectangle(bounds, colour) => {
+                    let (x1, y1) = scale.apply(&bounds.min);
+                    let (x2, y2) = scale.apply(&bounds.max);
+                    format!("<rect x=\"{:.1}\" y=\"{:.1}\" width=\"{:.1}\" height=\"{:.1}\" fill=\"none\" stroke=\"{}\" stroke-dasharray=\"4\"/>",
+                            x1, y1, x2 - x1, y2 - y1, colour.hex())
+                }
+                Shape::Points(points, colour) => {
+                    // dots are the size of a cell, unless that would be too small to see
+                    let radius = (scale.factor / 2.0).max(1.0);
+                    let dots = points.iter()
+                        .map(|p| scale.apply(p))
+                        .map(|(x, y)| format!("<circle cx=\"{:.1}\" cy=\"{:.1}\" r=\"{:.1}\"/>", x, y, radius))
+                        .collect::<String>();
+                    format!("<g fill=\"{}\">{}</g>", colour.hex(), dots)
+                }
+            });
+        }
+
+        svg.push("</svg>".to_owned());
+        svg.join("\n") + "\n"
+    }
+}
+
+/// Maps points onto the pixels of a drawing
+struct Scale {
+    origin: Point,
+    factor: f64,
+}
+
+impl Scale {
+    fn apply(&self, p: &Point) -> (f64, f64) {
+        ((p.x - self.origin.x) as f64 * self.factor + MARGIN, (p.y - self.origin.y) as f64 * self.factor + MARGIN)
+    }
+}
+
+// -------------------------------------------------------------------------------------------------
+// visualiser
+
+/// Writes the last drawing shown to an SVG file. Frames and events are ignored.
+pub struct SvgVisualiser {
+    file: File,
+    drawing: Option<Drawing>,
+}
+
+impl SvgVisualiser {
+    pub fn create(path: &str) -> io::Result<SvgVisualiser> {
+        Ok(SvgVisualiser { file: File::create(path)?, drawing: None })
+    }
+}
+
+impl Visualiser for SvgVisualiser {
+    fn frame(&mut self, _frame: &Frame) {}
+
+    fn event(&mut self, _event: &str) {}
+
+    fn drawing(&mut self, drawing: &Drawing) {
+        self.drawing = Some(drawing.clone());
+    }
+
+    fn finish(&mut self) -> io::Result<()> {
+        match &self.drawing {
+            Some(drawing) => self.file.write_all(drawing.to_svg(SIZE).as_bytes()),
+            None => Err(io::Error::other("Nothing to draw, no drawings were shown")),
+        }
+    }
+}
+
+// -------------------------------------------------------------------------------------------------
+// tests
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn scale_to_size() {
+        let path = Path::new(vec![Point::new(0, 0), Point::new(1000, 0), Point::new(1000, 500), Point::new(0, 500)]);
+        let svg = Drawing::new().path(&path, Colour(255, 0, 0)).to_svg(100.0);
+
+        assert!(svg.starts_with("<svg xmlns=\"http://www.w3.org/2000/svg\" width=\"120\" height=\"70\""));
+        assert!(svg.contains("<polygon points=\"10.0,10.0 110.0,10.0 110.0,60.0 10.0,60.0\" fill=\"#ff0000\""));
+    }
+
+    #[test]
+    fn extent_of_shapes() {
+        let drawing = Drawing::new()
+            .points(&[Point::new(-5, 2), Point::new(3, 4)], Colour(0, 0, 0))
+            .bounds(&Bounds { min: Point::new(0, -1), max: Point::new(1, 1) }, Colour(0, 0, 0));
+
+        assert_eq!(Some(Bounds { min: Point::new(-5, -1), max: Point::new(3, 4) }), drawing.extent());
+        assert_eq!(None, Drawing::new().extent());
+    }
+}
@@ -0,0 +1,304 @@
+use std::fs::File;
+use std::io::{self, IsTerminal, Read, Write};
+use std::process::{self, Command};
+use std::sync::mpsc::{self, Receiver};
+use std::thread;
+use std::time::{Duration, Instant};
+
+use crate::visualise::{Frame, Settings, Style, Theme, Visualiser};
+
+const CLEAR_SCREEN: &str = "\x1b[2J";
+const CLEAR_LINE: &str = "\x1b[K";
+const CLEAR_BELOW: &str = "\x1b[J";
+const HOME: &str = "\x1b[H";
+const HIDE_CURSOR: &str = "\x1b[?25l";
+const SHOW_CURSOR: &str = "\x1b[?25h";
+const RESET: &str = "\x1b[0m";
+
+/// Ctrl-C, which arrives as a key while the terminal's signals are turned off
+const INTERRUPT: u8 = 3;
+
+const HELP: &str = "[space] pause/resume  [s] step  [+/-] speed  [q] skip to end";
+
+/// Prints everything to stdout as it happens. Frames are shown in colour, unless the output is
+/// going somewhere other than a terminal.
+pub struct TerminalVisualiser {
+    theme: Theme,
+    colour: bool,
+}
+
+impl TerminalVisualiser {
+    pub fn new(theme: Theme) -> TerminalVisualiser {
+        TerminalVisualiser { theme, colour: io::stdout().is_terminal() }
+    }
+}
+
+impl Visualiser for TerminalVisualiser {
+    fn frame(&mut self, frame: &Frame) {
+        if !self.colour {
+            println!("{}", frame);
+            return;
+        }
+
+        let mut out = String::new();
+        for row in 0..frame.rows.len() {
+            out.push_str(&colourise(frame.cells(row), self.theme));
+            out.push('\n');
+        }
+        println!("{}", out);
+    }
+
+    fn event(&mut self, event: &str) {
+        println!("{}", event);
+    }
+}
+
+// -------------------------------------------------------------------------------------------------
+// animation
+
+/// Plays frames back as an animation, redrawing each one in place of the last at a steady rate,
+/// in colour. The latest event is shown above the frame.
+///
+/// If run in a terminal, it can be controlled while playing: space pauses and resumes, `s` steps
+/// forward one frame while paused, `+` and `-` change the speed, and `q` skips to the end.
+pub struct AnimatedVisualiser {
+    theme: Theme,
+    delay: Duration,
+    /// when the last frame was drawn
+    drawn: Option<Instant>,
+    frames: usize,
+    status: String,
+    paused: bool,
+    /// stop drawing frames, just keep the last one to show at the end
+    skipping: bool,
+    last: Option<Frame>,
+    keys: Option<Keys>,
+    hidden_cursor: bool,
+}
+
+impl AnimatedVisualiser {
+    pub fn new(settings: &Settings) -> AnimatedVisualiser {
+        AnimatedVisualiser {
+            theme: settings.theme,
+            delay: Duration::from_secs(1) / settings.fps.max(1),
+            drawn: None,
+            frames: 0,
+            status: String::new(),
+            paused: false,
+            skipping: false,
+            last: None,
+            keys: Keys::listen(),
+            hidden_cursor: false,
+        }
+    }
+
+    /// Wait until it's time for the next frame, acting on any keys pressed in the meantime
+    fn wait(&mut self) {
+        loop {
+            while let Some(key) = self.keys.as_ref().and_then(Keys::next) {
+                match key {
+                    b' ' => self.paused = !self.paused,
+                    b's' if self.paused => return,
+                    b'+' => self.delay /= 2,
+                    b'-' => self.delay = (self.delay * 2).max(Duration::from_millis(1)),
+                    b'q' => {
+                        self.skipping = true;
+                        return;
+                    }
+                    _ => {}
+                }
+            }
+
+            let due = self.drawn.map(|t| t + self.delay).unwrap_or_else(Instant::now);
+            if !self.paused && Instant::now() >= due { return; }
+            thread::sleep(Duration::from_millis(5));
+        }
+    }
+
+    fn draw(&mut self, frame: &Frame) {
+        let mut out = String::new();
+        if self.frames == 0 {
+            out.push_str(CLEAR_SCREEN);
+            out.push_str(HIDE_CURSOR);
+            self.hidden_cursor = true;
+        }
+        self.frames += 1;
+
+        out.push_str(HOME);
+        out.push_str(&format!("Frame {}  {}{}\n", self.frames, self.status, CLEAR_LINE));
+        for row in 0..frame.rows.len() {
+            out.push_str(&colourise(frame.cells(row), self.theme));
+            out.push_str(CLEAR_LINE);
+            out.push('\n');
+        }
+        if self.keys.is_some() {
+            out.push_str(&format!("\n{}{}\n", HELP, CLEAR_LINE));
+        }
+        out.push_str(CLEAR_BELOW);
+
+        let mut stdout = io::stdout().lock();
+        let _ = stdout.write_all(out.as_bytes()).and_then(|_| stdout.flush());
+        self.drawn = Some(Instant::now());
+    }
+
+    fn show_cursor(&mut self) {
+        if self.hidden_cursor {
+            print!("{}", SHOW_CURSOR);
+            self.hidden_cursor = false;
+        }
+    }
+}
+
+impl Visualiser for AnimatedVisualiser {
+    fn frame(&mut self, frame: &Frame) {
+        if !self.skipping { self.wait(); }
+
+        if self.skipping {
+            self.last = Some(frame.clone());
+        } else {
+            self.draw(frame);
+        }
+    }
+
+    fn event(&mut self, event: &str) {
+        self.status = event.trim().replace('\n', " ");
+    }
+
+    fn finish(&mut self) -> io::Result<()> {
+        // put the terminal back to normal before anything else is printed
+        self.keys = None;
+        if let Some(frame) = self.last.take() {
+            self.draw(&frame);
+        }
+        self.show_cursor();
+        io::stdout().flush()
+    }
+}
+
+impl Drop for AnimatedVisualiser {
+    fn drop(&mut self) {
+        self.show_cursor();
+    }
+}
+
+/// Add ANSI codes to a row of characters, to show each in its style as adjusted by the theme
+fn colourise(cells: impl Iterator<Item = (char, Style)>, theme: Theme) -> String {
+    let mut result = String::new();
+    let mut current = Style::new();
+    for (c, style) in cells {
+        let style = theme.apply(style);
+        if style != current {
+            // start from the default each time, so nothing is left over from the last style
+            if !current.is_plain() { result.push_str(RESET); }
+            if !style.is_plain() { result.push_str(&ansi(&style)); }
+            current = style;
+        }
+        result.push(c);
+    }
+    if !current.is_plain() { result.push_str(RESET); }
+    result
+}
+
+fn ansi(style: &Style) -> String {
+    let mut codes = Vec::new();
+    if style.bold { codes.push("1".to_owned()); }
+    if let Some(fg) = style.fg { codes.push(format!("38;2;{};{};{}", fg.0, fg.1, fg.2)); }
+    if let Some(bg) = style.bg { codes.push(format!("48;2;{};{};{}", bg.0, bg.1, bg.2)); }
+    format!("\x1b[{}m", codes.join(";"))
+}
+
+// -------------------------------------------------------------------------------------------------
+// controls
+
+/// Keys pressed in the terminal, which are read in the background. While listening, the terminal
+/// is switched out of line mode so that keys are available as soon as they're pressed, and its
+/// signals are turned off so that Ctrl-C can put the settings back before stopping.
+///
+/// The thread reading the keys can't be interrupted, so once the keys are dropped it stays blocked
+/// on stdin until the next key is pressed (which it throws away) or the program ends.
+struct Keys {
+    pressed: Receiver<u8>,
+    /// terminal settings to restore afterwards
+    saved: String,
+}
+
+impl Keys {
+    /// Start listening for keys, if there's a terminal to listen to
+    fn listen() -> Option<Keys> {
+        if !io::stdin().is_terminal() { return None; }
+
+        let saved = stty(&["-g"])?.trim().to_owned();
+        stty(&["-icanon", "-echo", "-isig"])?;
+
+        let (sender, pressed) = mpsc::channel();
+        let restore = saved.clone();
+        thread::spawn(move || {
+            let mut key = [0];
+            while io::stdin().read_exact(&mut key).is_ok() {
+                if key[0] == INTERRUPT {
+                    // stop as the interrupt would have, but leave the terminal as it was found
+                    print!("{}{}", RESET, SHOW_CURSOR);
+                    let _ = io::stdout().flush();
+                    stty(&[&restore]);
+                    process::exit(130);
+                }
+                if sender.send(key[0]).is_err() { break; }
+            }
+        });
+
+        Some(Keys { pressed, saved })
+    }
+
+    fn next(&self) -> Option<u8> {
+        self.pressed.try_recv().ok()
+    }
+}
+
+impl Drop for Keys {
+    fn drop(&mut self) {
+        stty(&[&self.saved]);
+    }
+}
+
+/// Change or query the terminal settings
+fn stty(args: &[&str]) -> Option<String> {
+    let output = Command::new("stty")
+        .args(args)
+        .stdin(File::open("/dev/tty").ok()?)
+        .output().ok()?;
+
+    if !output.status.success() { return None; }
+    String::from_utf8(output.stdout).ok()
+}
+
+// -------------------------------------------------------------------------------------------------
+// tests
+
+#[cfg(test)]
+mod tests {
+    use crate::visualise::{Colour, Palette};
+
+    use super::*;
+
+    #[test]
+    fn colourise_row() {
+        const PALETTE: &Palette = &[('#', Colour(255, 0, 0))];
+        let row = |text: &str| Frame::new(vec![text.to_owned()]).with_palette(PALETTE);
+
+        assert_eq!("..#", colourise(Frame::new(vec!["..#".to_owned()]).cells(0), Theme::Default));
+        assert_eq!(".\x1b[38;2;255;0;0m##\x1b[0m.", colourise(row(".##.").cells(0), Theme::Default));
+        assert_eq!("\x1b[38;2;255;0;0m#\x1b[0m", colourise(row("#").cells(0), Theme::Default));
+        assert_eq!("\x1b[1;38;2;255;0;0m#\x1b[0m", colourise(row("#").cells(0), Theme::HighContrast));
+    }
+
+    #[test]
+    fn colourise_styles() {
+        let frame = Frame::styled(vec!["ab.".to_owned()], vec![vec![
+            Style::new().bold(),
+            Style::new().fg(Colour(1, 2, 3)).bg(Colour(4, 5, 6)),
+            Style::new(),
+        ]]);
+
+        assert_eq!("\x1b[1ma\x1b[0m\x1b[38;2;1;2;3;48;2;4;5;6mb\x1b[0m.", colourise(frame.cells(0), Theme::Default));
+    }
+}
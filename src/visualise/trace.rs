@@ -0,0 +1,297 @@
+use std::borrow::Cow;
+use std::fs::File;
+use std::io::{self, BufWriter, Write};
+
+use crate::json::Json;
+use crate::utils::point::{Bounds, Point};
+use crate::visualise::{Colour, Drawing, Frame, Style, Visualiser};
+use crate::visualise::svg::Shape;
+
+/// Records frames, events and drawings to a file as they happen, so they can be replayed later.
+/// Each is written as a line of JSON:
+///
+/// ```text
+/// {"event":"Cycle 1:"}
+/// {"frame":["O.#","..."],"palette":[["O",230,230,230],["#",110,110,110]]}
+/// {"frame":["#."],"palette":[],"styles":[[[1,[255,210,60],null,true],[1,null,null,false]]]}
+/// {"drawing":[{"outline":[[0,0],[6,0],[6,5]],"colour":[255,210,60]},{"rectangle":[[0,0],[6,5]],"colour":[80,80,80]}]}
+/// ```
+///
+/// The styles of a styled frame are given for each row as runs of characters with the same
+/// style: the length of the run, its foreground and background colours, and whether it's bold.
+/// Each shape of a drawing is an `outline`, `rectangle` or `points`, given by its points.
+pub struct TraceVisualiser {
+    out: BufWriter<File>,
+    /// first error writing to the file, which is reported when finished
+    error: Option<io::Error>,
+}
+
+impl TraceVisualiser {
+    pub fn create(path: &str) -> io::Result<TraceVisualiser> {
+        Ok(TraceVisualiser { out: BufWriter::new(File::create(path)?), error: None })
+    }
+
+    fn write(&mut self, record: String) {
+        if self.error.is_none() {
+            self.error = writeln!(self.out, "{}", record).err();
+        }
+    }
+}
+
+impl Visualiser for TraceVisualiser {
+    fn frame(&mut self, frame: &Frame) {
+        self.write(frame_json(frame));
+    }
+
+    fn event(&mut self, event: &str) {
+        self.write(format!("{{\"event\":{}}}", quote(event)));
+    }
+
+    fn drawing(&mut self, drawing: &Drawing) {
+        self.write(drawing_json(drawing));
+    }
+
+    fn finish(&mut self) -> io::Result<()> {
+        match self.error.take() {
+            Some(e) => Err(e),
+            None => self.out.flush(),
+        }
+    }
+}
+
+fn frame_json(frame: &Frame) -> String {
+    let rows = frame.rows.iter().map(|r| quote(r)).collect::<Vec<_>>();
+    let palette = frame.palette.iter()
+        .map(|(c, Colour(r, g, b))| format!("[{},{},{},{}]", quote(&c.to_string()), r, g, b))
+        .collect::<Vec<_>>();
+
+    if frame.styles.is_empty() {
+        return format!("{{\"frame\":[{}],\"palette\":[{}]}}", rows.join(","), palette.join(","));
+    }
+
+    let colour = |colour: Option<Colour>| match colour {
+        Some(Colour(r, g, b)) => format!("[{},{},{}]", r, g, b),
+        None => "null".to_owned(),
+    };
+    let styles = frame.styles.iter()
+        .map(|row| {
+            let mut runs: Vec<(usize, Style)> = Vec::new();
+            for style in row {
+                match runs.last_mut() {
+                    Some((count, last)) if last == style => *count += 1,
+                    _ => runs.push((1, *style)),
+                }
+            }
+            let runs = runs.iter()
+                .map(|(count, style)| format!("[{},{},{},{}]", count, colour(style.fg), colour(style.bg), style.bold))
+                .collect::<Vec<_>>();
+            format!("[{}]", runs.join(","))
+        })
+        .collect::<Vec<_>>();
+    format!("{{\"frame\":[{}],\"palette\":[{}],\"styles\":[{}]}}", rows.join(","), palette.join(","), styles.join(","))
+}
+
+fn drawing_json(drawing: &Drawing) -> String {
+    let points = |points: &[Point]| points.iter().map(|p| format!("[{},{}]", p.x, p.y)).collect::<Vec<_>>().join(",");
+    let shapes = drawing.shapes.iter()
+        .map(|shape| {
+            let (kind, points, Colour(r, g, b)) = match shape {
+                Shape::Outline(path, colour) => ("outline", points(path), colour),
+                Shape::Rectangle(bounds, colour) => ("rectangle", points(&[bounds.min, bounds.max]), colour),
+                Shape::Points(dots, colour) => ("points", points(dots), colour),
+            };
+            format!("{{\"{}\":[{}],\"colour\":[{},{},{}]}}", kind, points, r, g, b)
+        })
+        .collect::<Vec<_>>();
+    format!("{{\"drawing\":[{}]}}", shapes.join(","))
+}
+
+fn quote(text: &str) -> String {
+    let mut quoted = String::with_capacity(text.len() + 2);
+    quoted.push('"');
+    for c in text.chars() {
+        match c {
+            '"' => quoted.push_str("\\\""),
+            '\\' => quoted.push_str("\\\\"),
+            '\n' => quoted.push_str("\\n"),
+            '\r' => quoted.push_str("\\r"),
+            '\t' => quoted.push_str("\\t"),
+            c if c.is_control() => quoted.push_str(&format!("\\u{:04x}", c as u32)),
+            c => quoted.push(c),
+        }
+    }
+    quoted.push('"');
+    quoted
+}
+
+// -------------------------------------------------------------------------------------------------
+// replay
+
+/// Send everything recorded in a trace to a visualiser, in the order it happened. Returns the
+/// number of records replayed.
+pub fn replay(trace: &str, visualiser: &mut dyn Visualiser) -> Result<usize, String> {
+    let mut count = 0;
+    for (i, line) in trace.lines().enumerate() {
+        if line.trim().is_empty() { continue; }
+
+        let record = Json::parse(line).map_err(|e| format!("{} on line {}", e, i + 1))?;
+        if let Some(event) = record.get("event") {
+            let event = event.as_str().ok_or_else(|| format!("Invalid event on line {}", i + 1))?;
+            visualiser.event(event);
+        } else if let Some(frame) = record.get("frame") {
+            let frame = parse_frame(frame, record.get("palette"), record.get("styles")).ok_or_else(|| format!("Invalid frame on line {}", i + 1))?;
+            visualiser.frame(&frame);
+        } else if let Some(drawing) = record.get("drawing") {
+            let drawing = parse_drawing(drawing).ok_or_else(|| format!("Invalid drawing on line {}", i + 1))?;
+            visualiser.drawing(&drawing);
+        } else {
+            return Err(format!("Unknown record on line {}", i + 1));
+        }
+        count += 1;
+    }
+    Ok(count)
+}
+
+fn parse_frame(rows: &Json, palette: Option<&Json>, styles: Option<&Json>) -> Option<Frame> {
+    let rows = rows.as_array()?.iter()
+        .map(|r| r.as_str().map(|r| r.to_owned()))
+        .collect::<Option<Vec<_>>>()?;
+
+    let palette = match palette {
+        None => Vec::new(),
+        Some(palette) => palette.as_array()?.iter()
+            .map(|entry| {
+                let [c, r, g, b] = entry.as_array()? else { return None };
+                let mut chars = c.as_str()?.chars();
+                let (Some(c), None) = (chars.next(), chars.next()) else { return None };
+                Some((c, Colour(r.as_u8()?, g.as_u8()?, b.as_u8()?)))
+            })
+            .collect::<Option<Vec<_>>>()?,
+    };
+
+    let styles = match styles {
+        None => Vec::new(),
+        Some(styles) => {
+            let styles = styles.as_array()?;
+            if styles.len() > rows.len() { return None; }
+            styles.iter().zip(&rows)
+                .map(|(runs, row)| parse_runs(runs, row.chars().count()))
+                .collect::<Option<Vec<_>>>()?
+        }
+    };
+
+    Some(Frame { rows, palette: Cow::Owned(palette), styles })
+}
+
+/// Expand a row of runs of styles into the style of each of its `length` characters
+fn parse_runs(runs: &Json, length: usize) -> Option<Vec<Style>> {
+    let colour = |colour: &Json| match colour {
+        Json::Null => Some(None),
+        colour => {
+            let [r, g, b] = colour.as_array()? else { return None };
+            Some(Some(Colour(r.as_u8()?, g.as_u8()?, b.as_u8()?)))
+        }
+    };
+
+    let mut styles = Vec::new();
+    for run in runs.as_array()? {
+        let [count, fg, bg, Json::Bool(bold)] = run.as_array()? else { return None };
+        let Json::Number(count) = count else { return None };
+        if count.fract() != 0.0 || *count < 0.0 || *count > (length - styles.len()) as f64 { return None; }
+        let style = Style { fg: colour(fg)?, bg: colour(bg)?, bold: *bold };
+        styles.extend(std::iter::repeat_n(style, *count as usize));
+    }
+    (styles.len() == length).then_some(styles)
+}
+
+fn parse_drawing(shapes: &Json) -> Option<Drawing> {
+    let shapes = shapes.as_array()?.iter()
+        .map(|shape| {
+            let [r, g, b] = shape.get("colour")?.as_array()? else { return None };
+            let colour = Colour(r.as_u8()?, g.as_u8()?, b.as_u8()?);
+            let points = |points: &Json| points.as_array()?.iter()
+                .map(|p| {
+                    let [x, y] = p.as_array()? else { return None };
+                    Some(Point::new(x.as_i64()?, y.as_i64()?))
+                })
+                .collect::<Option<Vec<_>>>();
+
+            if let Some(path) = shape.get("outline") {
+                Some(Shape::Outline(points(path)?, colour))
+            } else if let Some(corners) = shape.get("rectangle") {
+                let [min, max] = points(corners)?[..] else { return None };
+                Some(Shape::Rectangle(Bounds { min, max }, colour))
+            } else {
+                Some(Shape::Points(points(shape.get("points")?)?, colour))
+            }
+        })
+        .collect::<Option<Vec<_>>>()?;
+    Some(Drawing { shapes })
+}
+
+// -------------------------------------------------------------------------------------------------
+// tests
+
+#[cfg(test)]
+mod tests {
+    use crate::utils::path::Path;
+    use crate::visualise::{Palette, Recorder};
+
+    use super::*;
+
+    #[test]
+    fn record_and_replay() {
+        const PALETTE: &Palette = &[('#', Colour(255, 210, 60))];
+        let frame = Frame::new(vec!["#.\"".to_owned(), "\\#".to_owned()]).with_palette(PALETTE);
+
+        let trace = [format!("{{\"event\":{}}}", quote("Cycle 1:\n\tdone")), frame_json(&frame)].join("\n");
+        let mut recorder = Recorder::default();
+        assert_eq!(Ok(2), replay(&trace, &mut recorder));
+        assert_eq!(vec!["Cycle 1:\n\tdone".to_owned()], recorder.events);
+        assert_eq!(vec![frame], recorder.frames);
+    }
+
+    #[test]
+    fn record_and_replay_styles() {
+        let beam = Style::new().fg(Colour(255, 210, 60)).bold();
+        let frame = Frame::styled(vec!["##.".to_owned(), "".to_owned()], vec![
+            vec![beam, beam, Style::new().bg(Colour(0, 0, 1))],
+            vec![],
+        ]);
+
+        let trace = frame_json(&frame);
+        assert!(trace.ends_with("\"styles\":[[[2,[255,210,60],null,true],[1,null,[0,0,1],false]],[]]}"));
+        let mut recorder = Recorder::default();
+        assert_eq!(Ok(1), replay(&trace, &mut recorder));
+        assert_eq!(vec![frame], recorder.frames);
+    }
+
+    #[test]
+    fn record_and_replay_drawing() {
+        let outline = Path::new(vec![Point::new(0, 0), Point::new(6, 0), Point::new(6, -5_000_000_000)]);
+        let drawing = Drawing::new()
+            .path(&outline, Colour(255, 210, 60))
+            .bounds(&outline.bounds(), Colour(80, 80, 80))
+            .points(&[Point::new(1, 2)], Colour(0, 0, 1));
+
+        let trace = drawing_json(&drawing);
+        let mut recorder = Recorder::default();
+        assert_eq!(Ok(1), replay(&trace, &mut recorder));
+        assert_eq!(vec![drawing], recorder.drawings);
+    }
+
+    #[test]
+    fn invalid_trace() {
+        assert_eq!(Err("Unknown record on line 2".to_owned()), replay("{\"event\":\"a\"}\n{\"other\":1}", &mut Recorder::default()));
+        assert_eq!(Err("Invalid frame on line 1".to_owned()), replay("{\"frame\":[1]}", &mut Recorder::default()));
+        // styles must cover each character of their row exactly
+        let styled = |styles: &str| format!("{{\"frame\":[\"ab\"],\"palette\":[],\"styles\":[{}]}}", styles);
+        assert_eq!(Err("Invalid frame on line 1".to_owned()), replay(&styled("[[3,null,null,false]]"), &mut Recorder::default()));
+        assert_eq!(Err("Invalid frame on line 1".to_owned()), replay(&styled("[[1,null,null,false]]"), &mut Recorder::default()));
+        assert_eq!(Err("Invalid frame on line 1".to_owned()), replay(&styled("[[1e300,null,null,false]]"), &mut Recorder::default()));
+        assert_eq!(Err("Invalid frame on line 1".to_owned()), replay(&styled("[[2,null,null,false]],[]"), &mut Recorder::default()));
+        assert_eq!(Ok(1), replay(&styled("[[1,null,null,false],[1,null,null,true]]"), &mut Recorder::default()));
+        assert_eq!(Err("Invalid drawing on line 1".to_owned()), replay("{\"drawing\":[{\"rectangle\":[[0,0]],\"colour\":[1,2,3]}]}", &mut Recorder::default()));
+        assert!(replay("{\"event\":\"a\"", &mut Recorder::default()).is_err());
+    }
+}